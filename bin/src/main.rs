@@ -73,6 +73,11 @@ enum Commands {
         #[arg(short, long, default_value = "sp500")]
         universe: String,
 
+        /// Rebalance schedule (N, every:N, month-end, month-start, quarter-end,
+        /// a weekday such as friday, or dates:YYYY-MM-DD,...)
+        #[arg(short, long, default_value = "21")]
+        rebalance: String,
+
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
@@ -166,9 +171,10 @@ async fn run() -> Result<()> {
             start,
             end,
             universe,
+            rebalance,
             format,
         } => {
-            run_backtest(&signal, &start, &end, &universe, &format).await?;
+            run_backtest(&signal, &start, &end, &universe, &rebalance, &format).await?;
         }
         Commands::Score {
             signal,
@@ -559,21 +565,25 @@ async fn run_backtest(
     start: &str,
     end: &str,
     universe: &str,
+    rebalance: &str,
     format: &str,
 ) -> Result<()> {
     println!("\n╔══════════════════════════════════════════════════════════════╗");
     println!("║                       Backtesting                            ║");
     println!("╚══════════════════════════════════════════════════════════════╝\n");
 
-    println!("Signal:   {}", signal);
-    println!("Period:   {} to {}", start, end);
-    println!("Universe: {}", universe);
-    println!("Format:   {}", format);
+    println!("Signal:    {}", signal);
+    println!("Period:    {} to {}", start, end);
+    println!("Universe:  {}", universe);
+    println!("Rebalance: {}", rebalance);
+    println!("Format:    {}", format);
     println!();
 
-    // Parse dates
+    // Parse dates and rebalance schedule
     let start_date = data::parse_date(start).map_err(|e| anyhow::anyhow!("{}", e))?;
     let end_date = data::parse_date(end).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let schedule: tarifa_eval::RebalanceSchedule =
+        rebalance.parse().map_err(|e| anyhow::anyhow!("{}", e))?;
 
     // Determine symbols from universe
     let symbols: Vec<String> = if universe.to_lowercase() == "sp500" {
@@ -762,17 +772,18 @@ async fn run_backtest(
     let backtest_config = tarifa_eval::BacktestConfig {
         start_date,
         end_date,
-        rebalance_frequency: 21, // Monthly rebalancing
         transaction_cost_bps: 10.0,
         initial_capital: 1_000_000.0,
-        max_position_size: 0.1,
+        // Equal weight across 5 names per side
+        max_position_size: 0.2,
         min_position_size: 0.0,
         n_long: Some(5),
         n_short: Some(5),
         long_short: true,
+        ..Default::default()
     };
 
-    let backtest = tarifa_eval::Backtest::new(backtest_config);
+    let backtest = tarifa_eval::Backtest::new(backtest_config).with_schedule(schedule);
     let result = backtest.run(&signal_scores, &returns, &backtest_dates);
 
     // Display results
@@ -801,10 +812,21 @@ async fn run_backtest(
             result.annualized_volatility * 100.0
         );
         println!("  Sharpe Ratio:      {:>10.2}", result.sharpe_ratio);
+        println!(
+            "  Sortino Ratio:     {:>10.2}",
+            result.performance.sortino_ratio
+        );
         println!(
             "  Max Drawdown:      {:>10.2}%",
             result.max_drawdown * 100.0
         );
+        println!(
+            "  95% CVaR:          {:>10.2}%",
+            result.performance.cvar * 100.0
+        );
+        if let Some(final_nav) = result.nav.last() {
+            println!("  Final NAV:         {:>10.0}", final_nav);
+        }
         println!();
 
         println!("Trading Metrics:");
@@ -3,7 +3,7 @@
 use ndarray::Array1;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tarifa_traits::Result;
+use tarifa_traits::{CvSplit, Result};
 
 use crate::combiner::{Combiner, SignalScore};
 
@@ -94,6 +94,28 @@ impl ICWeightedCombiner {
         }
     }
 
+    /// Replace a signal's IC history with the training periods of a
+    /// cross-validation split.
+    ///
+    /// `ic_series` is indexed by period; non-finite values are skipped and
+    /// only the most recent `ic_lookback` training values are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal_name` - Name of the signal
+    /// * `ic_series` - Full IC time series of the signal
+    /// * `split` - Purged split whose training periods are used
+    pub fn fit_split(&mut self, signal_name: &str, ic_series: &[f64], split: &CvSplit) {
+        let history: Vec<f64> = split
+            .train_values(ic_series)
+            .into_iter()
+            .filter(|ic| ic.is_finite())
+            .collect();
+        let skip = history.len().saturating_sub(self.config.ic_lookback);
+        self.ic_history
+            .insert(signal_name.to_string(), history[skip..].to_vec());
+    }
+
     /// Compute exponentially-weighted average IC for a signal.
     fn compute_weighted_ic(&self, signal_name: &str) -> f64 {
         let history = match self.ic_history.get(signal_name) {
@@ -301,6 +323,27 @@ mod tests {
         assert_eq!(history.len(), 5);
     }
 
+    #[test]
+    fn test_ic_weight_fit_split() {
+        let config = ICWeightedConfig {
+            ic_lookback: 3,
+            decay_factor: 0.0,
+        };
+        let mut combiner = ICWeightedCombiner::new(config);
+        combiner.update_ic("sig1", 1.0);
+
+        let split = CvSplit {
+            train: vec![0, 1, 2, 5, 6],
+            test: vec![3, 4],
+            test_groups: vec![1],
+        };
+        let ic_series = vec![0.01, f64::NAN, 0.03, 0.50, 0.50, 0.06, 0.07];
+        combiner.fit_split("sig1", &ic_series, &split);
+
+        // Test periods and NaN skipped, previous history replaced
+        assert_eq!(combiner.ic_history["sig1"], vec![0.03, 0.06, 0.07]);
+    }
+
     #[test]
     fn test_ic_weight_negative_ic() {
         let mut combiner = ICWeightedCombiner::default();
@@ -3,19 +3,32 @@
 //! Provides a complete backtesting framework for evaluating trading signals
 //! with transaction costs, rebalancing, and performance metrics.
 
+use std::collections::BTreeMap;
+
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use tarifa_traits::{MarketData, Result, Symbol, TarifaError};
+
+use crate::benchmark::BenchmarkMetrics;
+use crate::borrow::ShortBorrow;
+use crate::costs::{CostContext, CostInputs, CostModel, FixedBpsCost, RebalanceCost};
+use crate::ledger::{Ledger, RebalanceRecord};
+use crate::panel::{Panel, PeriodCoverage, period_coverage};
+use crate::performance::{self, PerformanceStats};
+use crate::portfolio::{PortfolioConstructor, PortfolioContext, TopBottomConstructor};
+use crate::schedule::RebalanceSchedule;
 
 /// Backtesting configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BacktestConfig {
-    /// Start date for backtest
+    /// First date simulated (inclusive); [`NaiveDate::MIN`] leaves it unbounded
     pub start_date: NaiveDate,
-    /// End date for backtest
+    /// Last date simulated (inclusive); [`NaiveDate::MAX`] leaves it unbounded
     pub end_date: NaiveDate,
-    /// Rebalancing frequency in days
+    /// Rebalancing frequency in days (see [`Backtest::with_schedule`] for calendar rules)
     pub rebalance_frequency: usize,
-    /// Transaction cost (basis points)
+    /// Transaction cost in basis points of one-way turnover (`Σ|trade| / 2`),
+    /// charged on every rebalance including the initial portfolio build
     pub transaction_cost_bps: f64,
     /// Initial capital
     pub initial_capital: f64,
@@ -34,8 +47,8 @@ pub struct BacktestConfig {
 impl Default for BacktestConfig {
     fn default() -> Self {
         Self {
-            start_date: NaiveDate::from_ymd_opt(2020, 1, 1).expect("2020-01-01 is a valid date"),
-            end_date: NaiveDate::from_ymd_opt(2024, 12, 31).expect("2024-12-31 is a valid date"),
+            start_date: NaiveDate::MIN,
+            end_date: NaiveDate::MAX,
             rebalance_frequency: 21,
             transaction_cost_bps: 10.0,
             initial_capital: 1_000_000.0,
@@ -67,62 +80,110 @@ pub struct BacktestResult {
     pub annualized_volatility: f64,
     /// Average turnover
     pub avg_turnover: f64,
-    /// IC history
+    /// IC of each period's scores with the returns of that period, which
+    /// positions formed from the scores earn
     pub ic_history: Vec<f64>,
     /// Transaction costs (cumulative)
     pub total_transaction_costs: f64,
     /// Number of trades
     pub n_trades: usize,
+    /// Per-period coverage of scores and forward returns
+    #[serde(default)]
+    pub coverage: Vec<PeriodCoverage>,
+    /// Portfolio value in currency units, starting from `initial_capital`
+    #[serde(default)]
+    pub nav: Vec<f64>,
+    /// Transaction costs of each rebalance, broken down by cost component
+    #[serde(default)]
+    pub rebalance_costs: Vec<RebalanceCost>,
+    /// Short borrow fees accrued each period
+    #[serde(default)]
+    pub borrow_costs: Vec<f64>,
+    /// Short borrow fees (cumulative)
+    #[serde(default)]
+    pub total_borrow_cost: f64,
+    /// Per-rebalance record of targets, trades, costs and P&L by asset
+    #[serde(default)]
+    pub ledger: Ledger,
+    /// Performance relative to the benchmark, if one was supplied and lines
+    /// up with the simulated periods
+    #[serde(default)]
+    pub benchmark: Option<BenchmarkMetrics>,
+    /// Problems with the inputs that did not stop the run, such as a
+    /// benchmark that does not match the length of the returns
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Extended statistics of `returns` (calendar tables use each period's start date)
+    #[serde(default)]
+    pub performance: PerformanceStats,
 }
 
 impl BacktestResult {
     /// Calculate Sharpe ratio from returns.
     pub fn calculate_sharpe(returns: &[f64], trading_days_per_year: usize) -> f64 {
-        let valid_returns: Vec<f64> = returns.iter().copied().filter(|x| x.is_finite()).collect();
-
-        if valid_returns.len() < 2 {
-            return f64::NAN;
-        }
-
-        let mean = valid_returns.iter().sum::<f64>() / valid_returns.len() as f64;
-        let variance = valid_returns
-            .iter()
-            .map(|r| (r - mean).powi(2))
-            .sum::<f64>()
-            / (valid_returns.len() - 1) as f64;
-        let std = variance.sqrt();
-
-        if std == 0.0 {
-            f64::NAN
-        } else {
-            mean / std * (trading_days_per_year as f64).sqrt()
-        }
+        performance::sharpe_ratio(returns, trading_days_per_year)
     }
 
-    /// Calculate maximum drawdown.
+    /// Calculate maximum drawdown from cumulative returns.
     pub fn calculate_max_drawdown(cumulative_returns: &[f64]) -> f64 {
-        let mut max_dd = 0.0;
-        let mut peak = 0.0;
+        performance::wealth_drawdowns(cumulative_returns.iter().map(|c| 1.0 + c))
+            .into_iter()
+            .fold(0.0, f64::max)
+    }
 
-        for &cum_ret in cumulative_returns {
-            if cum_ret > peak {
-                peak = cum_ret;
-            }
-            let dd = (peak - cum_ret) / (1.0 + peak);
-            if dd > max_dd {
-                max_dd = dd;
+    /// Total transaction costs of each cost component over the backtest.
+    pub fn costs_by_component(&self) -> BTreeMap<String, f64> {
+        let mut totals = BTreeMap::new();
+        for rebalance in &self.rebalance_costs {
+            for (name, cost) in &rebalance.components {
+                *totals.entry(name.clone()).or_insert(0.0) += cost;
             }
         }
-
-        max_dd
+        totals
     }
 }
 
 /// Backtesting engine.
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct Backtest {
     /// Configuration
     config: BacktestConfig,
+    /// Portfolio construction strategy (top/bottom baskets from the config if unset)
+    constructor: Option<Box<dyn PortfolioConstructor>>,
+    /// Transaction cost components (flat `transaction_cost_bps` if empty)
+    cost_models: Vec<Box<dyn CostModel>>,
+    /// Short borrow fees and availability (free, unlimited shorting if unset)
+    borrow: Option<ShortBorrow>,
+    /// Rebalance schedule (every `rebalance_frequency` periods if unset)
+    schedule: Option<RebalanceSchedule>,
+    /// Minimum weight deviation from target before a name is traded
+    no_trade_band: Option<f64>,
+    /// Benchmark returns, indexed like the asset returns passed to `run`
+    benchmark: Option<Vec<f64>>,
+}
+
+impl std::fmt::Debug for Backtest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Backtest")
+            .field("config", &self.config)
+            .field(
+                "constructor",
+                &self.constructor.as_ref().map_or("top_bottom", |c| c.name()),
+            )
+            .field(
+                "cost_models",
+                &self
+                    .cost_models
+                    .iter()
+                    .map(|m| m.name())
+                    .collect::<Vec<_>>(),
+            )
+            .field("borrow", &self.borrow)
+            .field("schedule", &self.schedule())
+            .field("no_trade_band", &self.no_trade_band)
+            .field("benchmark", &self.benchmark.as_ref().map(Vec::len))
+            .finish()
+    }
 }
 
 impl Backtest {
@@ -141,11 +202,170 @@ impl Backtest {
     /// let backtest = Backtest::new(config);
     /// ```
     pub const fn new(config: BacktestConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            constructor: None,
+            cost_models: Vec::new(),
+            borrow: None,
+            schedule: None,
+            no_trade_band: None,
+            benchmark: None,
+        }
+    }
+
+    /// Use a custom portfolio construction strategy.
+    ///
+    /// Replaces the default equal-weight top/bottom baskets built from
+    /// `n_long`, `n_short` and `long_short`. Position limits from the config
+    /// are still applied to the constructor's output.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::{Backtest, BacktestConfig, QuantileConstructor};
+    ///
+    /// let backtest = Backtest::new(BacktestConfig::default())
+    ///     .with_constructor(QuantileConstructor::new(10, true));
+    /// ```
+    pub fn with_constructor(mut self, constructor: impl PortfolioConstructor + 'static) -> Self {
+        self.constructor = Some(Box::new(constructor));
+        self
+    }
+
+    /// Add a transaction cost component.
+    ///
+    /// Each call adds one component; the cost of a rebalance is the sum over
+    /// all components and is reported per component in
+    /// [`BacktestResult::rebalance_costs`]. Once any model is added, the flat
+    /// `transaction_cost_bps` from the config is no longer applied on its own;
+    /// add [`FixedBpsCost::per_turnover`] explicitly to keep it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::{Backtest, BacktestConfig, HalfSpreadCost, PerShareCommission, SquareRootImpact};
+    ///
+    /// let backtest = Backtest::new(BacktestConfig::default())
+    ///     .with_cost_model(PerShareCommission::new(0.005))
+    ///     .with_cost_model(HalfSpreadCost::new(5.0))
+    ///     .with_cost_model(SquareRootImpact::default());
+    /// ```
+    pub fn with_cost_model(mut self, model: impl CostModel + 'static) -> Self {
+        self.cost_models.push(Box::new(model));
+        self
+    }
+
+    /// Charge short borrow fees and respect a hard-to-borrow list.
+    ///
+    /// Fees accrue on the short book over the calendar days between
+    /// consecutive dates and are deducted from returns; they are also reported
+    /// separately in [`BacktestResult::borrow_costs`]. Per-symbol rates and the
+    /// hard-to-borrow list need symbol-keyed data ([`Backtest::run_panel`] or
+    /// [`Backtest::run_market_data`]); positional runs only see the default
+    /// rate.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::{Backtest, BacktestConfig, BorrowRates, ShortBorrow};
+    ///
+    /// let borrow = ShortBorrow::new(BorrowRates::from_frame(&borrow_df, 0.005)?)
+    ///     .with_hard_to_borrow(["GME".to_string()]);
+    /// let backtest = Backtest::new(BacktestConfig::default()).with_short_borrow(borrow);
+    /// ```
+    pub fn with_short_borrow(mut self, borrow: ShortBorrow) -> Self {
+        self.borrow = Some(borrow);
+        self
+    }
+
+    /// Rebalance on a calendar schedule instead of every
+    /// `rebalance_frequency` periods.
+    ///
+    /// The schedule is resolved against the dates passed to [`Backtest::run`]
+    /// after the configured date window is applied.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::{Backtest, BacktestConfig, RebalanceSchedule};
+    ///
+    /// let backtest = Backtest::new(BacktestConfig::default())
+    ///     .with_schedule(RebalanceSchedule::MonthEnd);
+    /// ```
+    pub fn with_schedule(mut self, schedule: RebalanceSchedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    /// Only trade names whose drifted weight is more than `band` away from
+    /// target.
+    ///
+    /// At each rebalance, names within the band keep their drifted weight and
+    /// incur no cost; the others are traded all the way to target.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::{Backtest, BacktestConfig};
+    ///
+    /// // Skip trades smaller than 50 bps of portfolio value
+    /// let backtest = Backtest::new(BacktestConfig::default()).with_no_trade_band(0.005);
+    /// ```
+    pub const fn with_no_trade_band(mut self, band: f64) -> Self {
+        self.no_trade_band = Some(band);
+        self
+    }
+
+    /// Compare performance against a benchmark return series.
+    ///
+    /// `returns[i]` must cover the same period as the asset returns at index
+    /// `i` passed to [`Backtest::run`] (from `dates[i]` to the next date). The
+    /// result then carries [`BenchmarkMetrics`] in
+    /// [`BacktestResult::benchmark`]; a benchmark whose length differs from
+    /// the asset returns is ignored with an entry in
+    /// [`BacktestResult::warnings`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::{Backtest, BacktestConfig};
+    ///
+    /// let result = Backtest::new(BacktestConfig::default())
+    ///     .with_benchmark(index_returns)
+    ///     .run(&signal_scores, &returns, &dates);
+    /// let active = result.benchmark.unwrap();
+    /// println!("IR vs index: {:.2}", active.information_ratio);
+    /// ```
+    pub fn with_benchmark(mut self, returns: Vec<f64>) -> Self {
+        self.benchmark = Some(returns);
+        self
+    }
+
+    /// The rebalance schedule in effect.
+    pub fn schedule(&self) -> RebalanceSchedule {
+        self.schedule
+            .clone()
+            .unwrap_or(RebalanceSchedule::EveryNDays(
+                self.config.rebalance_frequency,
+            ))
     }
 
     /// Run the backtest.
     ///
+    /// Only periods whose date falls within `[start_date, end_date]` of the
+    /// configuration are simulated; the default window is unbounded. Target
+    /// weights respect the configured position floor and cap (see
+    /// [`apply_position_limits`]).
+    ///
+    /// Between rebalances positions drift with realized returns, and each
+    /// rebalance trades from the drifted book to the new target.
+    ///
+    /// Transaction costs, including those of building the initial portfolio,
+    /// are deducted from the return of the period in which the trades are
+    /// made. Without market data only cost models that rely on traded weight
+    /// alone (such as [`FixedBpsCost`]) can charge anything; use
+    /// [`Backtest::run_with_cost_inputs`] to supply prices, spreads and volume.
+    ///
     /// # Arguments
     ///
     /// * `signal_scores` - Time series of signal scores for each asset
@@ -168,8 +388,44 @@ impl Backtest {
         signal_scores: &[Vec<f64>],
         returns: &[Vec<f64>],
         dates: &[NaiveDate],
+    ) -> BacktestResult {
+        self.run_with_cost_inputs(signal_scores, returns, dates, &CostInputs::default())
+    }
+
+    /// Run the backtest with market data for the transaction cost models.
+    ///
+    /// `cost_inputs` must be indexed like `signal_scores` and `returns`.
+    /// See [`Backtest::run`].
+    pub fn run_with_cost_inputs(
+        &self,
+        signal_scores: &[Vec<f64>],
+        returns: &[Vec<f64>],
+        dates: &[NaiveDate],
+        cost_inputs: &CostInputs,
+    ) -> BacktestResult {
+        self.simulate(signal_scores, returns, dates, None, cost_inputs)
+    }
+
+    /// Simulate the strategy; `symbols`, when known, is indexed like the
+    /// columns of `signal_scores`.
+    fn simulate(
+        &self,
+        signal_scores: &[Vec<f64>],
+        returns: &[Vec<f64>],
+        dates: &[NaiveDate],
+        symbols: Option<&[Symbol]>,
+        cost_inputs: &CostInputs,
     ) -> BacktestResult {
         let n_periods = signal_scores.len().min(returns.len()).min(dates.len());
+        let n_returns = returns.len();
+        let (start, end) = self.date_window(&dates[..n_periods]);
+        let cost_inputs = cost_inputs.slice(start, end);
+        let (signal_scores, returns, dates) = (
+            &signal_scores[start..end],
+            &returns[start..end],
+            &dates[start..end],
+        );
+        let n_periods = end - start;
 
         let mut portfolio_returns = Vec::with_capacity(n_periods);
         let mut cumulative_returns = Vec::with_capacity(n_periods);
@@ -179,51 +435,130 @@ impl Backtest {
         let mut current_positions: Vec<f64> = Vec::new();
         let mut cum_ret = 0.0;
         let mut total_transaction_costs = 0.0;
+        let mut rebalance_costs = Vec::new();
+        let mut borrow_costs = Vec::with_capacity(n_periods);
+        let mut ledger = Ledger {
+            symbols: symbols.map(<[Symbol]>::to_vec),
+            rebalances: Vec::new(),
+        };
         let mut n_trades = 0;
+        let rebalance_dates = self.schedule().resolve(dates);
 
         for i in 0..n_periods {
             // Rebalance check
-            let should_rebalance = i % self.config.rebalance_frequency == 0;
+            let should_rebalance = rebalance_dates[i];
+            let mut period_cost = 0.0;
 
             if should_rebalance {
-                let new_positions = self.construct_portfolio(&signal_scores[i]);
-
-                // Calculate turnover and transaction costs
+                let target = self.construct_portfolio(&signal_scores[i], &returns[..i]);
+                let mut new_positions = target.clone();
+                if let Some(borrow) = &self.borrow {
+                    borrow.block_new_shorts(&mut new_positions, &current_positions, symbols);
+                }
+                if let Some(band) = self.no_trade_band {
+                    apply_no_trade_band(&mut new_positions, &current_positions, band);
+                }
+                let previous: Vec<f64> = (0..new_positions.len())
+                    .map(|j| current_positions.get(j).copied().unwrap_or(0.0))
+                    .collect();
+                let trades: Vec<f64> = new_positions
+                    .iter()
+                    .zip(previous.iter())
+                    .map(|(new, old)| new - old)
+                    .collect();
+                let turnover = self.calculate_turnover(&previous, &new_positions);
+
+                // Turnover statistics only cover rebalances of an existing book
                 if !current_positions.is_empty() {
-                    let turnover = self.calculate_turnover(&current_positions, &new_positions);
                     turnover_history.push(turnover);
-
-                    let tc = turnover * self.config.transaction_cost_bps / 10000.0;
-                    total_transaction_costs += tc;
                     n_trades += 1;
                 }
 
+                let portfolio_value = self.config.initial_capital * (1.0 + cum_ret);
+                let (cost, trade_costs) = self.rebalance_cost(
+                    dates[i],
+                    turnover,
+                    &trades,
+                    &cost_inputs.context(i, portfolio_value),
+                );
+                period_cost = cost.total;
+                total_transaction_costs += cost.total;
+                rebalance_costs.push(cost);
+
+                ledger.rebalances.push(RebalanceRecord {
+                    date: dates[i],
+                    target_weights: target,
+                    pre_trade_weights: previous,
+                    trades,
+                    post_trade_weights: new_positions.clone(),
+                    costs: trade_costs,
+                    pnl: vec![0.0; new_positions.len()],
+                });
+
                 current_positions = new_positions;
             }
 
-            // Calculate portfolio return
-            let port_ret = if current_positions.is_empty() {
+            // Trading costs are paid out of the book before the period's
+            // return, so the traded weights are fractions of post-cost value
+            let post_cost = 1.0 - period_cost;
+
+            // Attribute this period's gross return to the positions held, in
+            // units of portfolio value before costs like the returns
+            if let Some(record) = ledger.rebalances.last_mut() {
+                for ((pnl, &w), &r) in record
+                    .pnl
+                    .iter_mut()
+                    .zip(current_positions.iter())
+                    .zip(returns[i].iter())
+                {
+                    if w.is_finite() && r.is_finite() {
+                        *pnl += post_cost * w * r;
+                    }
+                }
+            }
+
+            let borrow_cost = self.borrow.as_ref().map_or(0.0, |borrow| {
+                borrow.accrual(
+                    &current_positions,
+                    symbols,
+                    dates[i],
+                    accrual_days(dates, i),
+                )
+            });
+            borrow_costs.push(borrow_cost);
+
+            let gross_ret = if current_positions.is_empty() {
                 0.0
             } else {
                 self.calculate_portfolio_return(&current_positions, &returns[i])
             };
+            let port_ret = post_cost * (1.0 + gross_ret) - 1.0 - borrow_cost;
 
             portfolio_returns.push(port_ret);
             cum_ret = (1.0 + cum_ret) * (1.0 + port_ret) - 1.0;
             cumulative_returns.push(cum_ret);
 
-            // Calculate IC if we have forward returns
-            if i + 1 < n_periods {
-                let ic = crate::ic::calculate_ic(
-                    &ndarray::Array1::from_vec(signal_scores[i].clone()),
-                    &ndarray::Array1::from_vec(returns[i + 1].clone()),
-                );
-                ic_history.push(ic);
-            }
+            // Holdings drift with realized returns until the next trade
+            drift_weights(
+                &mut current_positions,
+                &returns[i],
+                (1.0 + port_ret) / post_cost - 1.0,
+            );
+
+            // IC against the same return the positions formed at `i` earn
+            let ic = crate::ic::calculate_ic(
+                &ndarray::Array1::from_vec(signal_scores[i].clone()),
+                &ndarray::Array1::from_vec(returns[i].clone()),
+            );
+            ic_history.push(ic);
         }
 
         let total_return = cum_ret;
-        let n_years = (dates.len() as f64) / 252.0;
+        let nav = cumulative_returns
+            .iter()
+            .map(|r| self.config.initial_capital * (1.0 + r))
+            .collect();
+        let n_years = (n_periods as f64) / 252.0;
         let annualized_return = if n_years > 0.0 {
             (1.0 + total_return).powf(1.0 / n_years) - 1.0
         } else {
@@ -231,19 +566,8 @@ impl Backtest {
         };
 
         let sharpe_ratio = BacktestResult::calculate_sharpe(&portfolio_returns, 252);
-        let max_drawdown = BacktestResult::calculate_max_drawdown(&cumulative_returns);
-
-        let annualized_volatility = if !portfolio_returns.is_empty() {
-            let mean = portfolio_returns.iter().sum::<f64>() / portfolio_returns.len() as f64;
-            let variance = portfolio_returns
-                .iter()
-                .map(|r| (r - mean).powi(2))
-                .sum::<f64>()
-                / (portfolio_returns.len() - 1) as f64;
-            variance.sqrt() * (252.0_f64).sqrt()
-        } else {
-            f64::NAN
-        };
+        let max_drawdown = performance::max_drawdown(&portfolio_returns);
+        let annualized_volatility = performance::annualized_volatility(&portfolio_returns, 252);
 
         let avg_turnover = if !turnover_history.is_empty() {
             turnover_history.iter().sum::<f64>() / turnover_history.len() as f64
@@ -251,6 +575,25 @@ impl Backtest {
             0.0
         };
 
+        let coverage = period_coverage(signal_scores, returns, dates);
+        let performance = PerformanceStats::compute(&portfolio_returns, dates, 252);
+
+        let mut warnings = Vec::new();
+        let benchmark = self.benchmark.as_ref().and_then(|bench| {
+            let metrics = if bench.len() == n_returns {
+                BenchmarkMetrics::compute(&portfolio_returns, &bench[start..end], 252)
+            } else {
+                Err(TarifaError::InvalidData(format!(
+                    "Benchmark has {} returns for {} asset return periods",
+                    bench.len(),
+                    n_returns
+                )))
+            };
+            metrics
+                .map_err(|e| warnings.push(format!("Benchmark ignored: {e}")))
+                .ok()
+        });
+
         BacktestResult {
             returns: portfolio_returns,
             cumulative_returns,
@@ -263,60 +606,126 @@ impl Backtest {
             ic_history,
             total_transaction_costs,
             n_trades,
+            coverage,
+            nav,
+            rebalance_costs,
+            total_borrow_cost: borrow_costs.iter().sum(),
+            borrow_costs,
+            ledger,
+            benchmark,
+            warnings,
+            performance,
         }
     }
 
-    /// Construct portfolio from signal scores.
-    fn construct_portfolio(&self, scores: &[f64]) -> Vec<f64> {
-        let n_assets = scores.len();
-        let mut positions = vec![0.0; n_assets];
-
-        // Create (index, score) pairs and filter valid scores
-        let mut indexed_scores: Vec<(usize, f64)> = scores
-            .iter()
-            .enumerate()
-            .filter_map(|(i, &s)| if s.is_finite() { Some((i, s)) } else { None })
-            .collect();
+    /// Run the backtest on a symbol-keyed panel.
+    ///
+    /// Unlike [`Backtest::run`], each column of the panel is pinned to one
+    /// symbol, so assets may enter and leave the universe without misaligning
+    /// scores and returns.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::Panel;
+    ///
+    /// let panel = Panel::from_frame(&long_df)?;
+    /// let result = backtest.run_panel(&panel);
+    /// ```
+    pub fn run_panel(&self, panel: &Panel) -> BacktestResult {
+        self.simulate(
+            &panel.scores,
+            &panel.forward_returns,
+            &panel.dates,
+            Some(&panel.symbols),
+            &panel.cost_inputs,
+        )
+    }
 
-        if indexed_scores.is_empty() {
-            return positions;
-        }
+    /// Run the backtest on long-format market data.
+    ///
+    /// The data must contain `symbol`, `date` and `score` columns plus either a
+    /// `return` or a `close` column. See [`Panel::from_market_data`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data cannot be aligned into a panel.
+    pub fn run_market_data(&self, data: &MarketData) -> Result<BacktestResult> {
+        let panel = Panel::from_market_data(data)?;
+        Ok(self.run_panel(&panel))
+    }
 
-        // Sort by score descending
-        indexed_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    /// Construct target portfolio weights from signal scores.
+    ///
+    /// Uses the configured [`PortfolioConstructor`] (equal-weight top/bottom
+    /// baskets by default) and then applies the position floor and cap.
+    fn construct_portfolio(&self, scores: &[f64], returns_history: &[Vec<f64>]) -> Vec<f64> {
+        let context = PortfolioContext::new(returns_history);
+        let mut positions = self.constructor.as_ref().map_or_else(
+            || TopBottomConstructor::from_config(&self.config).construct(scores, &context),
+            |constructor| constructor.construct(scores, &context),
+        );
 
-        if self.config.long_short {
-            // Long-short portfolio
-            let n_long = self.config.n_long.unwrap_or(n_assets / 2);
-            let n_short = self.config.n_short.unwrap_or(n_assets / 2);
+        apply_position_limits(
+            &mut positions,
+            self.config.min_position_size,
+            self.config.max_position_size,
+        );
 
-            // Long positions (top n_long)
-            let long_weight = if n_long > 0 { 1.0 / n_long as f64 } else { 0.0 };
-            for (idx, _score) in indexed_scores.iter().take(n_long.min(indexed_scores.len())) {
-                positions[*idx] = long_weight;
-            }
+        positions
+    }
 
-            // Short positions (bottom n_short)
-            let short_weight = if n_short > 0 {
-                -1.0 / n_short as f64
-            } else {
-                0.0
-            };
-            let start_idx = indexed_scores.len().saturating_sub(n_short);
-            for (idx, _score) in indexed_scores.iter().skip(start_idx) {
-                positions[*idx] = short_weight;
-            }
+    /// Price a rebalance with every configured cost component.
+    ///
+    /// Returns the per-component breakdown and the total cost of each trade.
+    fn rebalance_cost(
+        &self,
+        date: NaiveDate,
+        turnover: f64,
+        trades: &[f64],
+        context: &CostContext<'_>,
+    ) -> (RebalanceCost, Vec<f64>) {
+        let default_model = FixedBpsCost::per_turnover(self.config.transaction_cost_bps);
+        let models: Vec<&dyn CostModel> = if self.cost_models.is_empty() {
+            vec![&default_model]
         } else {
-            // Long-only portfolio
-            let n_long = self.config.n_long.unwrap_or(n_assets);
-            let weight = if n_long > 0 { 1.0 / n_long as f64 } else { 0.0 };
+            self.cost_models.iter().map(|m| m.as_ref()).collect()
+        };
 
-            for (idx, _score) in indexed_scores.iter().take(n_long.min(indexed_scores.len())) {
-                positions[*idx] = weight;
+        let mut components = BTreeMap::new();
+        let mut per_name = vec![0.0; trades.len()];
+        for model in models {
+            let costs = model.trade_costs(trades, context);
+            for (total, cost) in per_name.iter_mut().zip(costs.iter()) {
+                *total += cost;
             }
+            *components.entry(model.name().to_string()).or_insert(0.0) += costs.iter().sum::<f64>();
         }
 
-        positions
+        let cost = RebalanceCost {
+            date,
+            turnover,
+            total: components.values().sum(),
+            components,
+        };
+
+        (cost, per_name)
+    }
+
+    /// Index range of `dates` that falls inside the configured date window.
+    ///
+    /// Dates are assumed to be sorted in ascending order.
+    fn date_window(&self, dates: &[NaiveDate]) -> (usize, usize) {
+        let start = dates
+            .iter()
+            .position(|d| *d >= self.config.start_date)
+            .unwrap_or(dates.len());
+        let end = dates
+            .iter()
+            .rposition(|d| *d <= self.config.end_date)
+            .map_or(start, |i| (i + 1).max(start));
+
+        (start, end)
     }
 
     /// Calculate portfolio return given positions and asset returns.
@@ -345,6 +754,128 @@ impl Backtest {
     }
 }
 
+/// Let weights drift with one period of asset returns.
+///
+/// Each holding grows with its own return while portfolio value grows with
+/// `portfolio_return` (net of fees) from the value the weights were set
+/// against, so the weights remain fractions of the new portfolio value.
+/// Missing returns leave a holding's value unchanged.
+pub fn drift_weights(weights: &mut [f64], returns: &[f64], portfolio_return: f64) {
+    let growth = 1.0 + portfolio_return;
+    if growth <= 0.0 || !growth.is_finite() {
+        return;
+    }
+
+    for (w, r) in weights.iter_mut().zip(returns.iter()) {
+        let r = if r.is_finite() { *r } else { 0.0 };
+        *w = *w * (1.0 + r) / growth;
+    }
+}
+
+/// Keep current weights for names whose target is within `band` of them.
+fn apply_no_trade_band(target: &mut [f64], current: &[f64], band: f64) {
+    for (j, w) in target.iter_mut().enumerate() {
+        let held = current.get(j).copied().unwrap_or(0.0);
+        if (*w - held).abs() <= band {
+            *w = held;
+        }
+    }
+}
+
+/// Calendar days over which period `i` is held.
+///
+/// This is the gap to the next date; the last period reuses the previous gap
+/// (or one day if there is only one date).
+fn accrual_days(dates: &[NaiveDate], i: usize) -> i64 {
+    let gap = |a: usize, b: usize| (dates[b] - dates[a]).num_days();
+
+    if i + 1 < dates.len() {
+        gap(i, i + 1)
+    } else if i > 0 {
+        gap(i - 1, i)
+    } else {
+        1
+    }
+}
+
+/// Enforce per-name position floors and caps on a set of portfolio weights.
+///
+/// Long and short books are handled separately. Positions whose absolute weight
+/// is below `min_size` are closed and their weight is spread pro rata over the
+/// remaining names on the same side. Positions above `max_size` are clipped and
+/// the excess is redistributed pro rata over names that still have room under
+/// the cap. When every name on a side is at the cap the excess is dropped, so
+/// the side ends up less than fully invested rather than breaching the limit.
+///
+/// A non-positive or non-finite `max_size` disables the cap; a non-positive
+/// `min_size` disables the floor.
+pub fn apply_position_limits(weights: &mut [f64], min_size: f64, max_size: f64) {
+    for side in [1.0, -1.0] {
+        let mut book: Vec<(usize, f64)> = weights
+            .iter()
+            .enumerate()
+            .filter(|&(_, &w)| w * side > 0.0)
+            .map(|(i, &w)| (i, w.abs()))
+            .collect();
+
+        if book.is_empty() {
+            continue;
+        }
+
+        if min_size > 0.0 {
+            let dropped: f64 = book.iter().map(|&(_, w)| w).filter(|&w| w < min_size).sum();
+            book.retain(|&(_, w)| w >= min_size);
+            redistribute(&mut book, dropped, f64::INFINITY);
+        }
+
+        if max_size > 0.0 && max_size.is_finite() {
+            let mut excess = 0.0;
+            for (_, w) in book.iter_mut() {
+                if *w > max_size {
+                    excess += *w - max_size;
+                    *w = max_size;
+                }
+            }
+            redistribute(&mut book, excess, max_size);
+        }
+
+        for w in weights.iter_mut().filter(|w| **w * side > 0.0) {
+            *w = 0.0;
+        }
+        for (i, w) in book {
+            weights[i] = side * w;
+        }
+    }
+}
+
+/// Spread `amount` pro rata over the names in `book`, never exceeding `cap`.
+fn redistribute(book: &mut [(usize, f64)], mut amount: f64, cap: f64) {
+    const TOLERANCE: f64 = 1e-12;
+
+    while amount > TOLERANCE {
+        let room: f64 = book
+            .iter()
+            .filter(|&&(_, w)| w < cap - TOLERANCE)
+            .map(|&(_, w)| w)
+            .sum();
+        if room <= TOLERANCE {
+            break;
+        }
+
+        let mut overflow = 0.0;
+        for (_, w) in book.iter_mut().filter(|(_, w)| *w < cap - TOLERANCE) {
+            let target = *w + amount * *w / room;
+            if target > cap {
+                overflow += target - cap;
+                *w = cap;
+            } else {
+                *w = target;
+            }
+        }
+        amount = overflow;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -362,6 +893,8 @@ mod tests {
         assert_eq!(config.n_short, Some(50));
         assert_eq!(config.max_position_size, 0.1);
         assert_eq!(config.min_position_size, 0.0);
+        assert_eq!(config.start_date, NaiveDate::MIN);
+        assert_eq!(config.end_date, NaiveDate::MAX);
     }
 
     #[test]
@@ -481,6 +1014,10 @@ mod tests {
         let max_dd = BacktestResult::calculate_max_drawdown(&cumulative);
         // From peak 0.1 to -1.0: dd = (0.1 - (-1.0)) / (1 + 0.1) = 1.1 / 1.1 = 1.0
         assert!((max_dd - 1.0).abs() < 1e-10);
+
+        // Periods after the wipe-out stay at a full drawdown
+        let max_dd = BacktestResult::calculate_max_drawdown(&[0.0, 0.1, -1.0, -1.0]);
+        assert!((max_dd - 1.0).abs() < 1e-10);
     }
 
     #[test]
@@ -520,7 +1057,7 @@ mod tests {
         let backtest = Backtest::new(config);
 
         let scores = vec![0.5, -0.3, 0.8, -0.6, 0.1];
-        let positions = backtest.construct_portfolio(&scores);
+        let positions = backtest.construct_portfolio(&scores, &[]);
 
         // Check that we have 2 long and 2 short positions
         let n_long = positions.iter().filter(|&&p| p > 0.0).count();
@@ -538,12 +1075,13 @@ mod tests {
         let config = BacktestConfig {
             n_long: Some(3),
             long_short: false,
+            max_position_size: 1.0,
             ..Default::default()
         };
         let backtest = Backtest::new(config);
 
         let scores = vec![0.5, -0.3, 0.8, -0.6, 0.1];
-        let positions = backtest.construct_portfolio(&scores);
+        let positions = backtest.construct_portfolio(&scores, &[]);
 
         // Check that we have only long positions
         let n_long = positions.iter().filter(|&&p| p > 0.0).count();
@@ -562,7 +1100,7 @@ mod tests {
         let backtest = Backtest::new(config);
 
         let scores: Vec<f64> = vec![];
-        let positions = backtest.construct_portfolio(&scores);
+        let positions = backtest.construct_portfolio(&scores, &[]);
 
         assert!(positions.is_empty());
     }
@@ -573,12 +1111,13 @@ mod tests {
             n_long: Some(1),
             n_short: Some(0),
             long_short: false,
+            max_position_size: 1.0,
             ..Default::default()
         };
         let backtest = Backtest::new(config);
 
         let scores = vec![0.5];
-        let positions = backtest.construct_portfolio(&scores);
+        let positions = backtest.construct_portfolio(&scores, &[]);
 
         assert_eq!(positions.len(), 1);
         assert!((positions[0] - 1.0).abs() < 1e-10);
@@ -590,7 +1129,7 @@ mod tests {
         let backtest = Backtest::new(config);
 
         let scores = vec![f64::NAN, f64::NAN, f64::NAN];
-        let positions = backtest.construct_portfolio(&scores);
+        let positions = backtest.construct_portfolio(&scores, &[]);
 
         // All positions should be zero when all scores are NaN
         assert!(positions.iter().all(|&p| p == 0.0));
@@ -607,7 +1146,7 @@ mod tests {
         let backtest = Backtest::new(config);
 
         let scores = vec![f64::NAN, 0.5, 0.8, f64::NAN, -0.3];
-        let positions = backtest.construct_portfolio(&scores);
+        let positions = backtest.construct_portfolio(&scores, &[]);
 
         // Should only use valid (finite) scores for portfolio construction
         let n_long = positions.iter().filter(|&&p| p > 0.0).count();
@@ -626,7 +1165,7 @@ mod tests {
         let backtest = Backtest::new(config);
 
         let scores = vec![0.5, 0.3, 0.8];
-        let positions = backtest.construct_portfolio(&scores);
+        let positions = backtest.construct_portfolio(&scores, &[]);
 
         // Should use all available assets
         let n_long = positions.iter().filter(|&&p| p > 0.0).count();
@@ -643,12 +1182,13 @@ mod tests {
         let config = BacktestConfig {
             n_long: Some(5),
             long_short: false,
+            max_position_size: 1.0,
             ..Default::default()
         };
         let backtest = Backtest::new(config);
 
         let scores = vec![0.1, 0.2, 0.3, 0.4, 0.5];
-        let positions = backtest.construct_portfolio(&scores);
+        let positions = backtest.construct_portfolio(&scores, &[]);
 
         // Each position should be 0.2 (1/5)
         for &pos in &positions {
@@ -668,7 +1208,7 @@ mod tests {
 
         // Scores: index 2 (0.8) and 0 (0.5) are top, index 3 (-0.6) and 1 (-0.3) are bottom
         let scores = vec![0.5, -0.3, 0.8, -0.6, 0.1];
-        let positions = backtest.construct_portfolio(&scores);
+        let positions = backtest.construct_portfolio(&scores, &[]);
 
         // Top 2: index 2 and 0 should be long
         assert!(positions[2] > 0.0);
@@ -679,6 +1219,107 @@ mod tests {
         assert!(positions[1] < 0.0);
     }
 
+    #[test]
+    fn test_construct_portfolio_max_position_size() {
+        let config = BacktestConfig {
+            n_long: Some(4),
+            long_short: false,
+            max_position_size: 0.2,
+            ..Default::default()
+        };
+        let backtest = Backtest::new(config);
+
+        let positions = backtest.construct_portfolio(&[0.4, 0.3, 0.2, 0.1, 0.0, -0.1], &[]);
+
+        // Four names at 0.25 cannot all fit under a 0.2 cap
+        assert!(positions.iter().all(|&p| p <= 0.2 + 1e-12));
+        assert_eq!(positions.iter().filter(|&&p| p > 0.0).count(), 4);
+    }
+
+    #[test]
+    fn test_apply_position_limits_redistributes_excess() {
+        let mut weights = vec![0.6, 0.2, 0.2, -0.5, -0.5];
+        apply_position_limits(&mut weights, 0.0, 0.4);
+
+        // Long excess of 0.2 is split evenly over the two uncapped names
+        assert!((weights[0] - 0.4).abs() < 1e-12);
+        assert!((weights[1] - 0.3).abs() < 1e-12);
+        assert!((weights[2] - 0.3).abs() < 1e-12);
+        // Short side is fully capped, so its excess is dropped
+        assert!((weights[3] + 0.4).abs() < 1e-12);
+        assert!((weights[4] + 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_apply_position_limits_min_size() {
+        let mut weights = vec![0.5, 0.3, 0.15, 0.05];
+        apply_position_limits(&mut weights, 0.1, 1.0);
+
+        // The 0.05 position is closed and its weight reallocated pro rata
+        assert_eq!(weights[3], 0.0);
+        let sum: f64 = weights.iter().sum();
+        assert!((sum - 1.0).abs() < 1e-12);
+        assert!((weights[0] / weights[1] - 0.5 / 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_apply_position_limits_disabled() {
+        let mut weights = vec![0.7, 0.3, -1.0];
+        apply_position_limits(&mut weights, 0.0, 0.0);
+        assert_eq!(weights, vec![0.7, 0.3, -1.0]);
+    }
+
+    #[test]
+    fn test_construct_portfolio_custom_constructor() {
+        let config = BacktestConfig {
+            max_position_size: 0.4,
+            ..Default::default()
+        };
+        let backtest = Backtest::new(config)
+            .with_constructor(crate::portfolio::ScoreProportionalConstructor::new(true));
+
+        let positions = backtest.construct_portfolio(&[3.0, 1.0, -1.0, -1.0], &[]);
+
+        // 0.75 / 0.25 long: the excess over the cap spills into the smaller name,
+        // which then hits the cap too
+        assert!((positions[0] - 0.4).abs() < 1e-12);
+        assert!((positions[1] - 0.4).abs() < 1e-12);
+        assert!((positions[2] + 0.4).abs() < 1e-12);
+        assert!((positions[3] + 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_backtest_run_custom_constructor() {
+        struct AlwaysFirst;
+
+        impl PortfolioConstructor for AlwaysFirst {
+            fn construct(&self, scores: &[f64], _context: &PortfolioContext<'_>) -> Vec<f64> {
+                let mut weights = vec![0.0; scores.len()];
+                weights[0] = 1.0;
+                weights
+            }
+
+            fn name(&self) -> &str {
+                "always_first"
+            }
+        }
+
+        let config = BacktestConfig {
+            rebalance_frequency: 1,
+            transaction_cost_bps: 0.0,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let backtest = Backtest::new(config).with_constructor(AlwaysFirst);
+        assert!(format!("{backtest:?}").contains("always_first"));
+
+        let signal_scores = vec![vec![0.0, 1.0]; 3];
+        let returns = vec![vec![0.01, 0.05]; 3];
+        let result = backtest.run(&signal_scores, &returns, &create_test_dates(3));
+
+        assert!(result.returns.iter().all(|&r| (r - 0.01).abs() < 1e-12));
+    }
+
     // ==================== Turnover Tests ====================
 
     #[test]
@@ -853,7 +1494,7 @@ mod tests {
         let result_with_costs = backtest_with_costs.run(&signal_scores, &returns, &dates);
         let result_no_costs = backtest_no_costs.run(&signal_scores, &returns, &dates);
 
-        // With transaction costs, total costs should be tracked (after initial position)
+        // With transaction costs, total costs should be tracked
         // n_trades counts rebalances after the first one (periods 1,2,3,4 = 4 trades)
         assert_eq!(result_with_costs.n_trades, 4);
         assert!(result_with_costs.total_transaction_costs > 0.0);
@@ -990,7 +1631,7 @@ mod tests {
         };
         let backtest = Backtest::new(config);
 
-        // Perfect correlation between signals and next-period returns
+        // Perfect correlation between signals and the returns they earn
         let signal_scores = vec![
             vec![1.0, 2.0, 3.0, 4.0],
             vec![1.0, 2.0, 3.0, 4.0],
@@ -1007,8 +1648,8 @@ mod tests {
 
         let result = backtest.run(&signal_scores, &returns, &dates);
 
-        // IC history should have n_periods - 1 entries
-        assert_eq!(result.ic_history.len(), 3);
+        // One IC per period
+        assert_eq!(result.ic_history.len(), 4);
 
         // With perfect rank correlation, IC should be 1.0
         for ic in &result.ic_history {
@@ -1016,6 +1657,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_backtest_run_ic_same_period_returns() {
+        let config = BacktestConfig {
+            rebalance_frequency: 1,
+            transaction_cost_bps: 0.0,
+            n_long: Some(1),
+            n_short: Some(1),
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let backtest = Backtest::new(config);
+
+        // Scores rank the same-index returns perfectly and the next period's
+        // returns in reverse
+        let signal_scores = vec![
+            vec![1.0, 2.0, 3.0, 4.0],
+            vec![4.0, 3.0, 2.0, 1.0],
+            vec![1.0, 2.0, 3.0, 4.0],
+            vec![4.0, 3.0, 2.0, 1.0],
+        ];
+        let returns = vec![
+            vec![0.01, 0.02, 0.03, 0.04],
+            vec![0.04, 0.03, 0.02, 0.01],
+            vec![0.01, 0.02, 0.03, 0.04],
+            vec![0.04, 0.03, 0.02, 0.01],
+        ];
+        let dates = create_test_dates(4);
+
+        let result = backtest.run(&signal_scores, &returns, &dates);
+
+        assert_eq!(result.ic_history.len(), 4);
+        for ic in &result.ic_history {
+            assert!((*ic - 1.0).abs() < 1e-10);
+        }
+        // The P&L earns the same returns the IC measures
+        assert!(result.returns.iter().all(|&r| (r - 0.03).abs() < 1e-12));
+    }
+
     #[test]
     fn test_backtest_run_avg_turnover() {
         let config = BacktestConfig {
@@ -1120,6 +1799,15 @@ mod tests {
             ic_history: vec![0.05, 0.06],
             total_transaction_costs: 0.001,
             n_trades: 5,
+            coverage: vec![],
+            nav: vec![1_010_000.0, 1_030_200.0],
+            rebalance_costs: vec![],
+            borrow_costs: vec![],
+            total_borrow_cost: 0.0,
+            ledger: Ledger::default(),
+            benchmark: None,
+            warnings: vec![],
+            performance: PerformanceStats::default(),
         };
 
         // Verify serialization works
@@ -1130,6 +1818,495 @@ mod tests {
         assert_eq!(deserialized.n_trades, 5);
     }
 
+    #[test]
+    fn test_backtest_run_date_window() {
+        let dates = create_test_dates(10);
+        let config = BacktestConfig {
+            start_date: dates[2],
+            end_date: dates[6],
+            rebalance_frequency: 1,
+            transaction_cost_bps: 0.0,
+            n_long: Some(1),
+            long_short: false,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let backtest = Backtest::new(config);
+
+        let signal_scores: Vec<Vec<f64>> = (0..10).map(|_| vec![1.0, 0.0]).collect();
+        let returns: Vec<Vec<f64>> = (0..10).map(|i| vec![i as f64 / 100.0, 0.0]).collect();
+
+        let result = backtest.run(&signal_scores, &returns, &dates);
+
+        assert_eq!(result.returns.len(), 5);
+        assert!((result.returns[0] - 0.02).abs() < 1e-12);
+        assert!((result.returns[4] - 0.06).abs() < 1e-12);
+        assert_eq!(result.coverage.first().unwrap().date, dates[2]);
+    }
+
+    #[test]
+    fn test_backtest_run_date_window_outside_data() {
+        let config = BacktestConfig {
+            start_date: NaiveDate::from_ymd_opt(2030, 1, 1).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2031, 1, 1).unwrap(),
+            ..Default::default()
+        };
+        let backtest = Backtest::new(config);
+
+        let signal_scores = vec![vec![0.8, 0.6]; 5];
+        let returns = vec![vec![0.01, 0.02]; 5];
+        let result = backtest.run(&signal_scores, &returns, &create_test_dates(5));
+
+        assert!(result.returns.is_empty());
+        assert!(result.nav.is_empty());
+    }
+
+    #[test]
+    fn test_backtest_run_nav() {
+        let config = BacktestConfig {
+            rebalance_frequency: 1,
+            transaction_cost_bps: 0.0,
+            initial_capital: 250_000.0,
+            n_long: Some(1),
+            long_short: false,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let backtest = Backtest::new(config);
+
+        let signal_scores = vec![vec![1.0, 0.0]; 3];
+        let returns = vec![vec![0.10, 0.0], vec![-0.05, 0.0], vec![0.02, 0.0]];
+        let result = backtest.run(&signal_scores, &returns, &create_test_dates(3));
+
+        assert_eq!(result.nav.len(), 3);
+        assert!((result.nav[0] - 275_000.0).abs() < 1e-6);
+        assert!((result.nav[1] - 261_250.0).abs() < 1e-6);
+        assert!((result.nav[2] - 250_000.0 * (1.0 + result.total_return)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_backtest_run_coverage() {
+        let backtest = Backtest::default();
+        let signal_scores = vec![vec![0.8, f64::NAN, 0.3], vec![0.7, 0.6, f64::NAN]];
+        let returns = vec![vec![0.01, 0.02, f64::NAN], vec![0.01, 0.02, 0.03]];
+        let dates = create_test_dates(2);
+
+        let result = backtest.run(&signal_scores, &returns, &dates);
+
+        assert_eq!(result.coverage.len(), 2);
+        assert_eq!(result.coverage[0].n_scored, 2);
+        assert_eq!(result.coverage[0].n_aligned, 1);
+        assert_eq!(result.coverage[1].n_entered, 1);
+        assert_eq!(result.coverage[1].n_exited, 1);
+    }
+
+    #[test]
+    fn test_backtest_run_market_data() {
+        use polars::prelude::*;
+
+        let dates = create_test_dates(4);
+        let df = df! {
+            "symbol" => &["AAA", "BBB", "AAA", "BBB", "CCC", "AAA", "CCC", "AAA", "CCC"],
+            "date" => &[dates[0], dates[0], dates[1], dates[1], dates[1], dates[2], dates[2], dates[3], dates[3]],
+            "score" => &[1.0, -1.0, 1.0, -1.0, 0.0, 1.0, -1.0, 1.0, -1.0],
+            "return" => &[0.0, 0.0, 0.01, -0.01, 0.0, 0.01, -0.01, 0.01, -0.01],
+        }
+        .unwrap();
+
+        let config = BacktestConfig {
+            rebalance_frequency: 1,
+            transaction_cost_bps: 0.0,
+            n_long: Some(1),
+            n_short: Some(1),
+            long_short: true,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let result = Backtest::new(config)
+            .run_market_data(&MarketData::new(df))
+            .unwrap();
+
+        assert_eq!(result.returns.len(), 4);
+        // Long AAA / short the lowest-scored name earns 2% while both are held
+        assert!((result.returns[0] - 0.02).abs() < 1e-12);
+        assert!((result.returns[2] - 0.02).abs() < 1e-12);
+        assert_eq!(result.coverage[0].n_scored, 2);
+        assert_eq!(result.coverage[1].n_entered, 1);
+        assert_eq!(result.coverage[2].n_exited, 1);
+    }
+
+    #[test]
+    fn test_backtest_run_costs_deducted() {
+        let config = BacktestConfig {
+            rebalance_frequency: 2,
+            transaction_cost_bps: 10.0,
+            n_long: Some(1),
+            long_short: false,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let backtest = Backtest::new(config);
+
+        // Buy the first asset, hold, then switch to the second
+        let signal_scores = vec![vec![1.0, 0.0], vec![1.0, 0.0], vec![0.0, 1.0]];
+        let returns = vec![vec![0.0, 0.0]; 3];
+        let result = backtest.run(&signal_scores, &returns, &create_test_dates(3));
+
+        // Initial build is 0.5 of turnover at 10 bps; the book stays fully
+        // invested after paying for it, so the switch turns over 1.0
+        assert!((result.returns[0] + 0.0005).abs() < 1e-12);
+        assert!(result.returns[1].abs() < 1e-12);
+        assert!((result.returns[2] + 0.001).abs() < 1e-12);
+        assert!((result.total_transaction_costs - 0.0015).abs() < 1e-12);
+
+        assert_eq!(result.rebalance_costs.len(), 2);
+        assert!((result.rebalance_costs[1].turnover - 1.0).abs() < 1e-12);
+        assert!(
+            (result.ledger.rebalances[1]
+                .pre_trade_weights
+                .iter()
+                .sum::<f64>()
+                - 1.0)
+                .abs()
+                < 1e-12
+        );
+        assert!(
+            (result.costs_by_component()["fixed_bps"] - result.total_transaction_costs).abs()
+                < 1e-15
+        );
+    }
+
+    #[test]
+    fn test_backtest_run_cost_components() {
+        use crate::costs::{HalfSpreadCost, PerShareCommission, SquareRootImpact};
+
+        let config = BacktestConfig {
+            rebalance_frequency: 1,
+            initial_capital: 1_000_000.0,
+            n_long: Some(1),
+            long_short: false,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let backtest = Backtest::new(config)
+            .with_cost_model(PerShareCommission::new(0.01))
+            .with_cost_model(HalfSpreadCost::new(20.0))
+            .with_cost_model(SquareRootImpact::default());
+        assert!(format!("{backtest:?}").contains("market_impact"));
+
+        let signal_scores = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        let returns = vec![vec![0.0, 0.0]; 2];
+        let inputs = CostInputs {
+            prices: vec![vec![100.0, 50.0]; 2],
+            adv: vec![vec![100_000.0, 100_000.0]; 2],
+            volatility: vec![vec![0.02, 0.02]; 2],
+            ..Default::default()
+        };
+        let result =
+            backtest.run_with_cost_inputs(&signal_scores, &returns, &create_test_dates(2), &inputs);
+
+        let first = &result.rebalance_costs[0];
+        assert_eq!(first.components.len(), 3);
+        // $1M at $100 = 10,000 shares at $0.01
+        assert!((first.components["commission"] - 1e-4).abs() < 1e-12);
+        // No spread estimate: half of the 20 bps fallback
+        assert!((first.components["spread"] - 0.001).abs() < 1e-12);
+        assert!((first.components["market_impact"] - 0.02 * 0.1_f64.sqrt()).abs() < 1e-12);
+        assert!((first.total - first.components.values().sum::<f64>()).abs() < 1e-15);
+        assert!((result.returns[0] + first.total).abs() < 1e-12);
+
+        // The flat config cost is not applied once components are configured
+        assert!(!result.costs_by_component().contains_key("fixed_bps"));
+    }
+
+    #[test]
+    fn test_backtest_run_short_borrow() {
+        use crate::borrow::{BorrowRates, ShortBorrow};
+
+        let config = BacktestConfig {
+            rebalance_frequency: 1,
+            transaction_cost_bps: 0.0,
+            n_long: Some(1),
+            n_short: Some(1),
+            long_short: true,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let borrow = ShortBorrow::new(BorrowRates::Constant(0.036));
+        let backtest = Backtest::new(config).with_short_borrow(borrow);
+
+        let signal_scores = vec![vec![1.0, -1.0]; 3];
+        let returns = vec![vec![0.0, 0.0]; 3];
+        let result = backtest.run(&signal_scores, &returns, &create_test_dates(3));
+
+        // 1.0 short at 3.6% over one calendar day on actual/360
+        let daily = 0.036 / 360.0;
+        assert_eq!(result.borrow_costs.len(), 3);
+        assert!(
+            result
+                .borrow_costs
+                .iter()
+                .all(|&c| (c - daily).abs() < 1e-15)
+        );
+        assert!((result.total_borrow_cost - 3.0 * daily).abs() < 1e-15);
+        assert!((result.returns[0] + daily).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_backtest_run_hard_to_borrow() {
+        use crate::borrow::{BorrowRates, ShortBorrow};
+        use polars::prelude::*;
+        use std::collections::HashMap;
+
+        let dates = create_test_dates(2);
+        let df = df! {
+            "symbol" => &["AAA", "BBB", "HTB", "AAA", "BBB", "HTB"],
+            "date" => &[dates[0], dates[0], dates[0], dates[1], dates[1], dates[1]],
+            "score" => &[1.0, 0.0, -1.0, 1.0, 0.0, -1.0],
+            "return" => &[0.0; 6],
+        }
+        .unwrap();
+
+        let config = BacktestConfig {
+            rebalance_frequency: 1,
+            transaction_cost_bps: 0.0,
+            n_long: Some(1),
+            n_short: Some(1),
+            long_short: true,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let borrow = ShortBorrow::new(BorrowRates::PerSymbol {
+            rates: HashMap::from([("BBB".to_string(), 0.36)]),
+            default: 0.0,
+        })
+        .with_hard_to_borrow(["HTB".to_string()]);
+        let result = Backtest::new(config)
+            .with_short_borrow(borrow)
+            .run_market_data(&MarketData::new(df))
+            .unwrap();
+
+        // HTB cannot be shorted, so the short book stays empty and accrues nothing
+        assert_eq!(result.total_borrow_cost, 0.0);
+        assert!(result.returns.iter().all(|&r| r == 0.0));
+    }
+
+    #[test]
+    fn test_backtest_run_schedule() {
+        let config = BacktestConfig {
+            transaction_cost_bps: 0.0,
+            n_long: Some(1),
+            long_short: false,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let backtest = Backtest::new(config).with_schedule(RebalanceSchedule::MonthEnd);
+        assert_eq!(backtest.schedule(), RebalanceSchedule::MonthEnd);
+
+        // 2020-01-01 .. 2020-02-09: month ends on index 30 (Jan 31) and the last date
+        let dates = create_test_dates(40);
+        let signal_scores = vec![vec![1.0, 0.0]; 40];
+        let returns = vec![vec![0.01, 0.0]; 40];
+        let result = backtest.run(&signal_scores, &returns, &dates);
+
+        // Nothing is held before the first month end
+        assert!(result.returns[..30].iter().all(|&r| r == 0.0));
+        assert!((result.returns[30] - 0.01).abs() < 1e-12);
+        assert_eq!(result.rebalance_costs.len(), 2);
+        assert_eq!(result.rebalance_costs[0].date, dates[30]);
+        assert_eq!(result.n_trades, 1);
+    }
+
+    #[test]
+    fn test_drift_weights() {
+        let mut weights = vec![0.5, 0.5];
+        // Portfolio gains 5% when the first asset gains 10%
+        drift_weights(&mut weights, &[0.10, 0.0], 0.05);
+
+        assert!((weights[0] - 0.55 / 1.05).abs() < 1e-12);
+        assert!((weights[1] - 0.50 / 1.05).abs() < 1e-12);
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_backtest_run_drift() {
+        let config = BacktestConfig {
+            rebalance_frequency: 2,
+            transaction_cost_bps: 10.0,
+            n_long: Some(2),
+            long_short: false,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let backtest = Backtest::new(config);
+
+        let signal_scores = vec![vec![1.0, 0.5]; 3];
+        let returns = vec![vec![0.10, -0.10], vec![0.0, 0.0], vec![0.0, 0.0]];
+        let result = backtest.run(&signal_scores, &returns, &create_test_dates(3));
+
+        // 50/50 drifts to 55/45 of the post-cost portfolio, so restoring
+        // equal weights trades 5% on each side of a fully invested book
+        assert_eq!(result.n_trades, 1);
+        let drifted = &result.ledger.rebalances[1].pre_trade_weights;
+        assert!((drifted.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!((result.rebalance_costs[1].turnover - 0.05).abs() < 1e-12);
+        assert!(result.rebalance_costs[1].total > 0.0);
+    }
+
+    #[test]
+    fn test_backtest_run_no_trade_band() {
+        let config = BacktestConfig {
+            rebalance_frequency: 1,
+            transaction_cost_bps: 10.0,
+            n_long: Some(2),
+            long_short: false,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+
+        let signal_scores = vec![vec![1.0, 0.5]; 3];
+        let returns = vec![vec![0.01, -0.01]; 3];
+        let dates = create_test_dates(3);
+
+        let banded = Backtest::new(config.clone()).with_no_trade_band(0.05).run(
+            &signal_scores,
+            &returns,
+            &dates,
+        );
+        let unbanded = Backtest::new(config).run(&signal_scores, &returns, &dates);
+
+        // Drift stays within the band, so only the initial build is traded
+        assert!(banded.rebalance_costs[1..].iter().all(|c| c.total == 0.0));
+        assert!(unbanded.rebalance_costs[1..].iter().all(|c| c.total > 0.0));
+        assert!(banded.total_transaction_costs < unbanded.total_transaction_costs);
+    }
+
+    #[test]
+    fn test_backtest_run_ledger() {
+        let config = BacktestConfig {
+            rebalance_frequency: 2,
+            transaction_cost_bps: 10.0,
+            n_long: Some(1),
+            n_short: Some(1),
+            long_short: true,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let backtest = Backtest::new(config);
+
+        let signal_scores = vec![
+            vec![1.0, 0.0, -1.0],
+            vec![1.0, 0.0, -1.0],
+            vec![0.0, 1.0, -1.0],
+            vec![0.0, 1.0, -1.0],
+        ];
+        let returns = vec![vec![0.02, 0.01, -0.01]; 4];
+        let result = backtest.run(&signal_scores, &returns, &create_test_dates(4));
+        let ledger = &result.ledger;
+
+        assert!(ledger.symbols.is_none());
+        assert_eq!(ledger.rebalances.len(), 2);
+
+        let first = &ledger.rebalances[0];
+        assert_eq!(first.target_weights, vec![1.0, 0.0, -1.0]);
+        assert_eq!(first.pre_trade_weights, vec![0.0; 3]);
+        assert_eq!(first.trades, first.post_trade_weights);
+
+        // Per-name costs add up to the rebalance total
+        for (record, cost) in ledger.rebalances.iter().zip(result.rebalance_costs.iter()) {
+            assert!((record.costs.iter().sum::<f64>() - cost.total).abs() < 1e-15);
+        }
+
+        // The second rebalance starts from the drifted book and sells asset 0
+        let second = &ledger.rebalances[1];
+        assert!((second.pre_trade_weights[0] - 1.0).abs() > 1e-6);
+        assert!((second.trades[0] + second.pre_trade_weights[0]).abs() < 1e-12);
+
+        // Per-name P&L net of costs adds up to the returns of the holding period
+        let gross: f64 = first.pnl.iter().sum();
+        let costs: f64 = first.costs.iter().sum();
+        assert_eq!(first.pnl[1], 0.0);
+        assert!((gross - costs - (result.returns[0] + result.returns[1])).abs() < 1e-12);
+
+        let df = ledger.to_frame().unwrap();
+        assert_eq!(df.height(), 5);
+    }
+
+    #[test]
+    fn test_backtest_run_benchmark() {
+        let dates = create_test_dates(6);
+        let config = BacktestConfig {
+            start_date: dates[1],
+            rebalance_frequency: 1,
+            transaction_cost_bps: 0.0,
+            n_long: Some(1),
+            long_short: false,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+
+        let signal_scores = vec![vec![1.0, 0.0]; 6];
+        let returns: Vec<Vec<f64>> = [0.5, 0.01, -0.02, 0.03, 0.01, -0.01]
+            .iter()
+            .map(|&r| vec![r, 0.0])
+            .collect();
+        // The benchmark tracks the held asset except in the excluded first period
+        let benchmark: Vec<f64> = returns.iter().map(|r| r[0]).collect();
+
+        let result = Backtest::new(config.clone()).run(&signal_scores, &returns, &dates);
+        assert!(result.benchmark.is_none());
+
+        let result =
+            Backtest::new(config)
+                .with_benchmark(benchmark)
+                .run(&signal_scores, &returns, &dates);
+        let metrics = result.benchmark.unwrap();
+
+        assert_eq!(metrics.n_obs, 5);
+        assert!(metrics.tracking_error.abs() < 1e-12);
+        assert!((metrics.beta - 1.0).abs() < 1e-12);
+        assert!(metrics.active_max_drawdown.abs() < 1e-12);
+        assert!(result.warnings.is_empty());
+
+        // A benchmark that does not cover every period cannot be aligned
+        let result = Backtest::new(BacktestConfig {
+            start_date: dates[1],
+            ..Default::default()
+        })
+        .with_benchmark(vec![0.01; 5])
+        .run(&signal_scores, &returns, &dates);
+        assert!(result.benchmark.is_none());
+        assert_eq!(result.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_backtest_run_performance() {
+        let config = BacktestConfig {
+            rebalance_frequency: 1,
+            transaction_cost_bps: 0.0,
+            n_long: Some(1),
+            long_short: false,
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let dates = create_test_dates(40);
+        let signal_scores = vec![vec![1.0, 0.0]; 40];
+        let returns: Vec<Vec<f64>> = (0..40)
+            .map(|i| vec![if i % 3 == 0 { -0.01 } else { 0.01 }, 0.0])
+            .collect();
+
+        let result = Backtest::new(config).run(&signal_scores, &returns, &dates);
+        let stats = &result.performance;
+
+        assert!((stats.hit_rate - 26.0 / 40.0).abs() < 1e-12);
+        assert!(stats.sortino_ratio > result.sharpe_ratio);
+        assert!(stats.var > 0.0);
+        // 2020-01-01 .. 2020-02-09 spans two months of one year
+        assert_eq!(stats.monthly_returns.len(), 2);
+        assert_eq!(stats.yearly_returns.len(), 1);
+        assert!((stats.yearly_returns[0].total_return - result.total_return).abs() < 1e-12);
+    }
+
     #[test]
     fn test_backtest_config_serialization() {
         let config = BacktestConfig::default();
@@ -0,0 +1,205 @@
+//! Benchmark-relative performance.
+//!
+//! Compares a portfolio return series with a benchmark over the same periods:
+//! active return and risk, regression beta and alpha, capture ratios and the
+//! drawdown of relative performance.
+
+use serde::{Deserialize, Serialize};
+use tarifa_traits::{Result, TarifaError};
+
+use crate::performance;
+use crate::stats::{mean, std_dev};
+
+/// Active performance of a portfolio relative to a benchmark.
+///
+/// Annualized figures use arithmetic scaling of per-period means (and square
+/// root scaling of standard deviations).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkMetrics {
+    /// Per-period active returns (portfolio minus benchmark), NaN where
+    /// either return is missing
+    pub active_returns: Vec<f64>,
+    /// Annualized mean active return
+    pub active_return: f64,
+    /// Annualized standard deviation of active returns
+    pub tracking_error: f64,
+    /// Active return divided by tracking error
+    pub information_ratio: f64,
+    /// Regression beta of portfolio on benchmark returns
+    pub beta: f64,
+    /// Annualized regression alpha (Jensen's alpha with a zero risk-free rate)
+    pub alpha: f64,
+    /// Mean portfolio return over mean benchmark return in up periods
+    pub up_capture: f64,
+    /// Mean portfolio return over mean benchmark return in down periods
+    pub down_capture: f64,
+    /// Maximum drawdown of portfolio wealth relative to benchmark wealth
+    pub active_max_drawdown: f64,
+    /// Number of periods with both returns available
+    pub n_obs: usize,
+}
+
+impl BenchmarkMetrics {
+    /// Compute benchmark-relative metrics.
+    ///
+    /// Periods where either return is missing are skipped by the summary
+    /// statistics.
+    ///
+    /// # Arguments
+    ///
+    /// * `returns` - Portfolio returns
+    /// * `benchmark` - Benchmark returns over the same periods
+    /// * `periods_per_year` - Periods per year used for annualization
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the two series differ in length, since their
+    /// periods could not be matched by position.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::BenchmarkMetrics;
+    ///
+    /// let metrics = BenchmarkMetrics::compute(&result.returns, &index_returns, 252)?;
+    /// println!("Tracking error: {:.2}%", metrics.tracking_error * 100.0);
+    /// ```
+    pub fn compute(returns: &[f64], benchmark: &[f64], periods_per_year: usize) -> Result<Self> {
+        if returns.len() != benchmark.len() {
+            return Err(TarifaError::InvalidData(format!(
+                "Benchmark has {} returns for {} portfolio periods",
+                benchmark.len(),
+                returns.len()
+            )));
+        }
+
+        let pairs: Vec<(f64, f64)> = returns
+            .iter()
+            .zip(benchmark.iter())
+            .filter(|(p, b)| p.is_finite() && b.is_finite())
+            .map(|(&p, &b)| (p, b))
+            .collect();
+        let n_obs = pairs.len();
+        let annual = periods_per_year as f64;
+
+        let active: Vec<f64> = pairs.iter().map(|(p, b)| p - b).collect();
+        let portfolio: Vec<f64> = pairs.iter().map(|&(p, _)| p).collect();
+        let bench: Vec<f64> = pairs.iter().map(|&(_, b)| b).collect();
+
+        let active_return = mean(&active) * annual;
+        let tracking_error = std_dev(&active) * annual.sqrt();
+        let information_ratio = if tracking_error > 0.0 {
+            active_return / tracking_error
+        } else {
+            f64::NAN
+        };
+
+        let bench_var = variance(&bench);
+        let beta = if bench_var > 0.0 {
+            covariance(&portfolio, &bench) / bench_var
+        } else {
+            f64::NAN
+        };
+        let alpha = (mean(&portfolio) - beta * mean(&bench)) * annual;
+
+        let capture = |up: bool| {
+            let (p, b): (Vec<f64>, Vec<f64>) = pairs
+                .iter()
+                .filter(|&&(_, b)| if up { b > 0.0 } else { b < 0.0 })
+                .copied()
+                .unzip();
+            let bench_mean = mean(&b);
+            if bench_mean.is_finite() && bench_mean != 0.0 {
+                mean(&p) / bench_mean
+            } else {
+                f64::NAN
+            }
+        };
+
+        let relative: Vec<f64> = pairs
+            .iter()
+            .map(|&(p, b)| (1.0 + p) / (1.0 + b) - 1.0)
+            .collect();
+
+        Ok(Self {
+            active_returns: returns.iter().zip(benchmark).map(|(p, b)| p - b).collect(),
+            active_return,
+            tracking_error,
+            information_ratio,
+            beta,
+            alpha,
+            up_capture: capture(true),
+            down_capture: capture(false),
+            active_max_drawdown: performance::max_drawdown(&relative),
+            n_obs,
+        })
+    }
+}
+
+fn covariance(x: &[f64], y: &[f64]) -> f64 {
+    if x.len() < 2 {
+        return f64::NAN;
+    }
+    let (mx, my) = (mean(x), mean(y));
+    x.iter()
+        .zip(y.iter())
+        .map(|(a, b)| (a - mx) * (b - my))
+        .sum::<f64>()
+        / (x.len() - 1) as f64
+}
+
+fn variance(values: &[f64]) -> f64 {
+    covariance(values, values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_identical_series() {
+        let returns = vec![0.01, -0.02, 0.015, 0.005, -0.01];
+        let metrics = BenchmarkMetrics::compute(&returns, &returns, 252).unwrap();
+
+        assert_eq!(metrics.n_obs, 5);
+        assert_relative_eq!(metrics.active_return, 0.0);
+        assert_relative_eq!(metrics.tracking_error, 0.0);
+        assert!(metrics.information_ratio.is_nan());
+        assert_relative_eq!(metrics.beta, 1.0, epsilon = 1e-12);
+        assert_relative_eq!(metrics.alpha, 0.0, epsilon = 1e-12);
+        assert_relative_eq!(metrics.up_capture, 1.0);
+        assert_relative_eq!(metrics.down_capture, 1.0);
+        assert_relative_eq!(metrics.active_max_drawdown, 0.0);
+    }
+
+    #[test]
+    fn test_levered_portfolio() {
+        let benchmark = vec![0.01, -0.02, 0.015, 0.005, -0.01];
+        let returns: Vec<f64> = benchmark.iter().map(|b| 2.0 * b + 0.001).collect();
+        let metrics = BenchmarkMetrics::compute(&returns, &benchmark, 252).unwrap();
+
+        assert_relative_eq!(metrics.beta, 2.0, epsilon = 1e-12);
+        assert_relative_eq!(metrics.alpha, 0.001 * 252.0, epsilon = 1e-10);
+        assert!(metrics.up_capture > 2.0);
+        assert!(metrics.down_capture < 2.0);
+        assert!(metrics.active_max_drawdown > 0.0);
+        assert_eq!(metrics.active_returns.len(), 5);
+    }
+
+    #[test]
+    fn test_missing_values_skipped() {
+        let returns = vec![0.01, f64::NAN, 0.02, 0.0];
+        let benchmark = vec![0.0, 0.01, f64::NAN, 0.0];
+        let metrics = BenchmarkMetrics::compute(&returns, &benchmark, 252).unwrap();
+
+        assert_eq!(metrics.n_obs, 2);
+        assert!(metrics.beta.is_nan());
+        // Active returns stay aligned with the input periods
+        assert_eq!(metrics.active_returns.len(), 4);
+        assert_eq!(metrics.active_returns[0], 0.01);
+        assert!(metrics.active_returns[1].is_nan() && metrics.active_returns[2].is_nan());
+
+        assert!(BenchmarkMetrics::compute(&returns, &benchmark[..3], 252).is_err());
+    }
+}
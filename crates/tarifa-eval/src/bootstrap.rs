@@ -0,0 +1,306 @@
+//! Bootstrap confidence intervals.
+//!
+//! Resamples a time series with a block scheme that preserves serial
+//! dependence and reports percentile confidence intervals, standard errors and
+//! two-sided p-values for a statistic of the series. Convenience functions cover
+//! the Sharpe ratio of a return series and the mean IC and IR of an IC series.
+//! Non-finite observations are dropped before resampling, and every run is
+//! seeded so results are reproducible.
+
+use serde::{Deserialize, Serialize};
+
+use crate::metrics::{InformationRatio, MetricsConfig};
+use crate::performance;
+use crate::rng::SplitMix64;
+use crate::stats::{mean, std_dev};
+
+/// Resampling scheme used to draw bootstrap samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BootstrapMethod {
+    /// Stationary bootstrap (Politis and Romano, 1994): blocks of geometric
+    /// length with mean `block_length`
+    #[default]
+    Stationary,
+    /// Circular block bootstrap: blocks of exactly `block_length`, wrapping
+    /// around the end of the series
+    CircularBlock,
+}
+
+/// Configuration for bootstrap resampling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootstrapConfig {
+    /// Resampling scheme
+    pub method: BootstrapMethod,
+    /// Block length (mean block length for the stationary bootstrap); 1 gives
+    /// the i.i.d. bootstrap
+    pub block_length: usize,
+    /// Number of bootstrap resamples
+    pub n_resamples: usize,
+    /// Confidence level of the interval (e.g. 0.95)
+    pub confidence: f64,
+    /// Seed of the random number generator
+    pub seed: u64,
+}
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        Self {
+            method: BootstrapMethod::Stationary,
+            block_length: 10,
+            n_resamples: 1000,
+            confidence: 0.95,
+            seed: 42,
+        }
+    }
+}
+
+/// Bootstrap confidence interval for a statistic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfidenceInterval {
+    /// Statistic on the original sample
+    pub estimate: f64,
+    /// Lower percentile bound
+    pub lower: f64,
+    /// Upper percentile bound
+    pub upper: f64,
+    /// Standard deviation of the bootstrap distribution
+    pub std_error: f64,
+    /// Two-sided p-value for the null hypothesis that the statistic is zero
+    pub p_value: f64,
+    /// Confidence level of the interval
+    pub confidence: f64,
+    /// Number of resamples with a finite statistic
+    pub n_resamples: usize,
+}
+
+impl ConfidenceInterval {
+    /// Bootstrap an arbitrary statistic of a series.
+    ///
+    /// The p-value is computed from the bootstrap distribution re-centred on
+    /// zero: the share of resamples whose deviation from the estimate is at
+    /// least as large as the estimate itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - Observations in time order
+    /// * `config` - Resampling configuration
+    /// * `statistic` - Statistic to evaluate on each sample
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::{BootstrapConfig, ConfidenceInterval};
+    ///
+    /// let ci = ConfidenceInterval::bootstrap(&returns, &BootstrapConfig::default(), |r| {
+    ///     r.iter().sum::<f64>() / r.len() as f64
+    /// });
+    /// println!("Mean return in [{:.4}, {:.4}]", ci.lower, ci.upper);
+    /// ```
+    pub fn bootstrap<F>(values: &[f64], config: &BootstrapConfig, statistic: F) -> Self
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let values: Vec<f64> = values.iter().copied().filter(|x| x.is_finite()).collect();
+        let estimate = if values.is_empty() {
+            f64::NAN
+        } else {
+            statistic(&values)
+        };
+
+        let mut rng = SplitMix64::new(config.seed);
+        let mut sample = vec![0.0; values.len()];
+        let mut draws = Vec::with_capacity(config.n_resamples);
+        if values.len() >= 2 {
+            for _ in 0..config.n_resamples {
+                resample(&values, config, &mut rng, &mut sample);
+                let stat = statistic(&sample);
+                if stat.is_finite() {
+                    draws.push(stat);
+                }
+            }
+        }
+
+        if draws.is_empty() || !estimate.is_finite() {
+            return Self {
+                estimate,
+                lower: f64::NAN,
+                upper: f64::NAN,
+                std_error: f64::NAN,
+                p_value: f64::NAN,
+                confidence: config.confidence,
+                n_resamples: draws.len(),
+            };
+        }
+
+        let alpha = (1.0 - config.confidence.clamp(0.0, 1.0)) / 2.0;
+        let extreme = draws
+            .iter()
+            .filter(|&&d| (d - estimate).abs() >= estimate.abs())
+            .count();
+
+        Self {
+            estimate,
+            lower: performance::quantile(&draws, alpha),
+            upper: performance::quantile(&draws, 1.0 - alpha),
+            std_error: std_dev(&draws),
+            p_value: (extreme + 1) as f64 / (draws.len() + 1) as f64,
+            confidence: config.confidence,
+            n_resamples: draws.len(),
+        }
+    }
+
+    /// Whether the interval excludes zero.
+    pub fn excludes_zero(&self) -> bool {
+        self.lower > 0.0 || self.upper < 0.0
+    }
+}
+
+/// Bootstrap confidence interval for the annualized Sharpe ratio.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use tarifa_eval::{BootstrapConfig, sharpe_ratio_ci};
+///
+/// let ci = sharpe_ratio_ci(&result.returns, 252, &BootstrapConfig::default());
+/// println!("Sharpe {:.2} (p = {:.3})", ci.estimate, ci.p_value);
+/// ```
+pub fn sharpe_ratio_ci(
+    returns: &[f64],
+    periods_per_year: usize,
+    config: &BootstrapConfig,
+) -> ConfidenceInterval {
+    ConfidenceInterval::bootstrap(returns, config, |r| {
+        performance::sharpe_ratio(r, periods_per_year)
+    })
+}
+
+/// Bootstrap confidence interval for the mean of an IC series.
+pub fn mean_ic_ci(ic_series: &[f64], config: &BootstrapConfig) -> ConfidenceInterval {
+    ConfidenceInterval::bootstrap(ic_series, config, mean)
+}
+
+/// Bootstrap confidence interval for the information ratio of an IC series.
+///
+/// The IR is computed with [`InformationRatio::calculate`], so annualization
+/// and the minimum observation count follow `metrics`.
+pub fn information_ratio_ci(
+    ic_series: &[f64],
+    metrics: &MetricsConfig,
+    config: &BootstrapConfig,
+) -> ConfidenceInterval {
+    ConfidenceInterval::bootstrap(ic_series, config, |ic| {
+        InformationRatio::calculate(ic, metrics).ir
+    })
+}
+
+/// Fill `sample` with one bootstrap resample of `values`.
+fn resample(values: &[f64], config: &BootstrapConfig, rng: &mut SplitMix64, sample: &mut [f64]) {
+    let n = values.len();
+    let block_length = config.block_length.max(1);
+    let mut pos = rng.next_index(n);
+    let mut run = 0;
+
+    for slot in sample.iter_mut() {
+        let new_block = match config.method {
+            BootstrapMethod::Stationary => rng.next_f64() < 1.0 / block_length as f64,
+            BootstrapMethod::CircularBlock => run == block_length,
+        };
+        if new_block {
+            pos = rng.next_index(n);
+            run = 0;
+        }
+        *slot = values[pos];
+        pos = (pos + 1) % n;
+        run += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noisy_series(n: usize, drift: f64) -> Vec<f64> {
+        let mut rng = SplitMix64::new(11);
+        (0..n)
+            .map(|_| drift + (rng.next_f64() - 0.5) * 0.02)
+            .collect()
+    }
+
+    #[test]
+    fn test_seeded_reproducible() {
+        let r = noisy_series(250, 0.001);
+        let config = BootstrapConfig::default();
+        let a = sharpe_ratio_ci(&r, 252, &config);
+        let b = sharpe_ratio_ci(&r, 252, &config);
+
+        assert_eq!(a.lower, b.lower);
+        assert_eq!(a.upper, b.upper);
+        assert_eq!(a.p_value, b.p_value);
+
+        let c = sharpe_ratio_ci(&r, 252, &BootstrapConfig { seed: 7, ..config });
+        assert_ne!(a.lower, c.lower);
+    }
+
+    #[test]
+    fn test_interval_brackets_estimate() {
+        let ic = noisy_series(200, 0.005);
+        for method in [BootstrapMethod::Stationary, BootstrapMethod::CircularBlock] {
+            let config = BootstrapConfig {
+                method,
+                block_length: 5,
+                ..Default::default()
+            };
+            let ci = mean_ic_ci(&ic, &config);
+
+            assert!(ci.lower < ci.estimate && ci.estimate < ci.upper);
+            assert!(ci.std_error > 0.0);
+            assert!(ci.excludes_zero());
+            assert!(ci.p_value < 0.05);
+            assert_eq!(ci.n_resamples, config.n_resamples);
+        }
+    }
+
+    #[test]
+    fn test_no_signal_not_significant() {
+        let mut ic = noisy_series(200, 0.0);
+        let mean = ic.iter().sum::<f64>() / ic.len() as f64;
+        ic.iter_mut().for_each(|x| *x -= mean);
+        let metrics = MetricsConfig {
+            min_observations: 2,
+            ..Default::default()
+        };
+        let ci = information_ratio_ci(&ic, &metrics, &BootstrapConfig::default());
+
+        assert!(ci.estimate.is_finite());
+        assert!(!ci.excludes_zero());
+        assert!(ci.p_value > 0.05);
+    }
+
+    #[test]
+    fn test_degenerate_input() {
+        let ci = mean_ic_ci(&[f64::NAN, 0.01], &BootstrapConfig::default());
+        assert!(ci.lower.is_nan());
+        assert!(ci.p_value.is_nan());
+        assert_eq!(ci.n_resamples, 0);
+    }
+
+    #[test]
+    fn test_circular_blocks_are_contiguous() {
+        let values: Vec<f64> = (0..20).map(f64::from).collect();
+        let config = BootstrapConfig {
+            method: BootstrapMethod::CircularBlock,
+            block_length: 4,
+            ..Default::default()
+        };
+        let mut rng = SplitMix64::new(3);
+        let mut sample = vec![0.0; values.len()];
+        resample(&values, &config, &mut rng, &mut sample);
+
+        for block in sample.chunks(4) {
+            for pair in block.windows(2) {
+                assert_eq!(pair[1], (pair[0] + 1.0) % 20.0);
+            }
+        }
+    }
+}
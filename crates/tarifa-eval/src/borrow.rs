@@ -0,0 +1,274 @@
+//! Short borrow costs.
+//!
+//! Shorting a stock requires borrowing it, and lenders charge an annualized
+//! fee on the value of the borrowed shares. [`ShortBorrow`] accrues that fee
+//! on the short book of a backtest and blocks new shorts in names that are
+//! hard to borrow.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::NaiveDate;
+use polars::prelude::*;
+use tarifa_traits::{Result, Symbol, TarifaError};
+
+use crate::panel::{DATE_COLUMN, SYMBOL_COLUMN, float_column};
+
+/// Default name of the annualized borrow rate column.
+pub const BORROW_RATE_COLUMN: &str = "borrow_rate";
+
+/// Default day-count basis for fee accrual (actual/360, the stock loan convention).
+pub const DEFAULT_DAY_COUNT: f64 = 360.0;
+
+/// Annualized borrow rates, as fractions (0.01 = 1% per year).
+#[derive(Debug, Clone)]
+pub enum BorrowRates {
+    /// Same rate for every symbol on every date
+    Constant(f64),
+    /// Fixed rate per symbol
+    PerSymbol {
+        /// Rate of each symbol
+        rates: HashMap<Symbol, f64>,
+        /// Rate for symbols not in the map
+        default: f64,
+    },
+    /// Rate observations per symbol, applied as of each date
+    TimeVarying {
+        /// Observations of each symbol sorted by date
+        rates: HashMap<Symbol, Vec<(NaiveDate, f64)>>,
+        /// Rate for symbols or dates without a prior observation
+        default: f64,
+    },
+}
+
+impl BorrowRates {
+    /// Build time-varying rates from a long-format DataFrame.
+    ///
+    /// The frame must contain `symbol`, `date` and `borrow_rate` columns. On
+    /// each date a symbol is charged its most recent rate observed on or
+    /// before that date, or `default` if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required column is missing or has an unexpected
+    /// type.
+    pub fn from_frame(df: &DataFrame, default: f64) -> Result<Self> {
+        Self::from_frame_with_column(df, BORROW_RATE_COLUMN, default)
+    }
+
+    /// Build time-varying rates from a DataFrame with a custom rate column.
+    ///
+    /// See [`BorrowRates::from_frame`].
+    pub fn from_frame_with_column(df: &DataFrame, rate_col: &str, default: f64) -> Result<Self> {
+        let symbols = df
+            .column(SYMBOL_COLUMN)
+            .map_err(|_| TarifaError::MissingColumn(SYMBOL_COLUMN.to_string()))?
+            .as_materialized_series()
+            .str()?
+            .clone();
+        let dates = df
+            .column(DATE_COLUMN)
+            .map_err(|_| TarifaError::MissingColumn(DATE_COLUMN.to_string()))?
+            .cast(&DataType::Date)?;
+        let values = float_column(df, rate_col)?;
+
+        let mut rates: HashMap<Symbol, Vec<(NaiveDate, f64)>> = HashMap::new();
+        let rows = symbols
+            .into_iter()
+            .zip(dates.as_materialized_series().date()?.as_date_iter())
+            .zip(values);
+        for ((symbol, date), rate) in rows {
+            if let (Some(symbol), Some(date), Some(rate)) = (symbol, date, rate) {
+                rates
+                    .entry(symbol.to_string())
+                    .or_default()
+                    .push((date, rate));
+            }
+        }
+        for observations in rates.values_mut() {
+            observations.sort_by_key(|&(date, _)| date);
+        }
+
+        Ok(Self::TimeVarying { rates, default })
+    }
+
+    /// Annualized rate for `symbol` on `date`.
+    ///
+    /// Without a symbol (positional backtests) the default rate applies.
+    pub fn rate(&self, symbol: Option<&str>, date: NaiveDate) -> f64 {
+        match self {
+            Self::Constant(rate) => *rate,
+            Self::PerSymbol { rates, default } => symbol
+                .and_then(|s| rates.get(s))
+                .copied()
+                .unwrap_or(*default),
+            Self::TimeVarying { rates, default } => symbol
+                .and_then(|s| rates.get(s))
+                .and_then(|obs| {
+                    let n = obs.partition_point(|&(d, _)| d <= date);
+                    n.checked_sub(1).map(|i| obs[i].1)
+                })
+                .unwrap_or(*default),
+        }
+    }
+}
+
+/// Short borrow fee and availability model.
+#[derive(Debug, Clone)]
+pub struct ShortBorrow {
+    /// Annualized borrow rates
+    pub rates: BorrowRates,
+    /// Symbols in which new shorts cannot be opened
+    pub hard_to_borrow: HashSet<Symbol>,
+    /// Days per year used to turn annual rates into daily accruals
+    pub day_count: f64,
+}
+
+impl ShortBorrow {
+    /// Create a borrow model with no hard-to-borrow names.
+    pub fn new(rates: BorrowRates) -> Self {
+        Self {
+            rates,
+            hard_to_borrow: HashSet::new(),
+            day_count: DEFAULT_DAY_COUNT,
+        }
+    }
+
+    /// Set the hard-to-borrow list.
+    pub fn with_hard_to_borrow(mut self, symbols: impl IntoIterator<Item = Symbol>) -> Self {
+        self.hard_to_borrow = symbols.into_iter().collect();
+        self
+    }
+
+    /// Set the day-count basis for fee accrual.
+    pub const fn with_day_count(mut self, day_count: f64) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    /// Borrow fee, as a fraction of portfolio value, for holding `positions`
+    /// over `days` calendar days starting on `date`.
+    ///
+    /// Only negative (short) weights accrue fees. `symbols`, when given, is
+    /// indexed like `positions`.
+    pub fn accrual(
+        &self,
+        positions: &[f64],
+        symbols: Option<&[Symbol]>,
+        date: NaiveDate,
+        days: i64,
+    ) -> f64 {
+        let year_fraction = days.max(0) as f64 / self.day_count;
+
+        positions
+            .iter()
+            .enumerate()
+            .filter(|&(_, &w)| w.is_finite() && w < 0.0)
+            .map(|(i, &w)| {
+                let symbol = symbols.and_then(|s| s.get(i)).map(String::as_str);
+                -w * self.rates.rate(symbol, date) * year_fraction
+            })
+            .sum()
+    }
+
+    /// Prevent target weights from opening or adding to shorts in
+    /// hard-to-borrow names.
+    ///
+    /// Existing shorts may be kept or covered, but never increased. Blocked
+    /// weight is not redistributed, so the short book can end up less than
+    /// fully invested. Requires `symbols`; without them nothing is blocked.
+    pub fn block_new_shorts(
+        &self,
+        target: &mut [f64],
+        current: &[f64],
+        symbols: Option<&[Symbol]>,
+    ) {
+        let Some(symbols) = symbols else {
+            return;
+        };
+
+        for (i, w) in target.iter_mut().enumerate() {
+            let blocked = symbols
+                .get(i)
+                .is_some_and(|s| self.hard_to_borrow.contains(s));
+            if blocked && *w < 0.0 {
+                let held = current.get(i).copied().unwrap_or(0.0).min(0.0);
+                *w = w.max(held);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    fn symbols(names: &[&str]) -> Vec<Symbol> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_rates() {
+        assert_eq!(BorrowRates::Constant(0.01).rate(Some("AAA"), date(1)), 0.01);
+
+        let per_symbol = BorrowRates::PerSymbol {
+            rates: HashMap::from([("GME".to_string(), 0.25)]),
+            default: 0.005,
+        };
+        assert_eq!(per_symbol.rate(Some("GME"), date(1)), 0.25);
+        assert_eq!(per_symbol.rate(Some("AAA"), date(1)), 0.005);
+        assert_eq!(per_symbol.rate(None, date(1)), 0.005);
+    }
+
+    #[test]
+    fn test_rates_from_frame() {
+        let df = df! {
+            "symbol" => &["AAA", "AAA", "BBB"],
+            "date" => &[date(10), date(3), date(5)],
+            "borrow_rate" => &[0.20, 0.02, 0.05],
+        }
+        .unwrap();
+        let rates = BorrowRates::from_frame(&df, 0.01).unwrap();
+
+        assert_eq!(rates.rate(Some("AAA"), date(1)), 0.01);
+        assert_eq!(rates.rate(Some("AAA"), date(3)), 0.02);
+        assert_eq!(rates.rate(Some("AAA"), date(9)), 0.02);
+        assert_eq!(rates.rate(Some("AAA"), date(15)), 0.20);
+        assert_eq!(rates.rate(Some("BBB"), date(6)), 0.05);
+        assert_eq!(rates.rate(Some("CCC"), date(6)), 0.01);
+
+        let missing = BorrowRates::from_frame(&df.drop("borrow_rate").unwrap(), 0.0);
+        assert!(matches!(missing, Err(TarifaError::MissingColumn(_))));
+    }
+
+    #[test]
+    fn test_accrual() {
+        let borrow = ShortBorrow::new(BorrowRates::Constant(0.036));
+
+        // Long positions accrue nothing; 0.5 short at 3.6% for 10 days on actual/360
+        let fee = borrow.accrual(&[0.5, -0.5], None, date(1), 10);
+        assert!((fee - 0.5 * 0.036 * 10.0 / 360.0).abs() < 1e-15);
+        assert_eq!(borrow.accrual(&[0.5, 0.5], None, date(1), 10), 0.0);
+    }
+
+    #[test]
+    fn test_block_new_shorts() {
+        let names = symbols(&["AAA", "HTB", "OLD"]);
+        let borrow = ShortBorrow::new(BorrowRates::Constant(0.0))
+            .with_hard_to_borrow(symbols(&["HTB", "OLD"]));
+
+        let mut target = vec![-0.3, -0.3, -0.4];
+        let current = vec![0.0, 0.1, -0.2];
+        borrow.block_new_shorts(&mut target, &current, Some(&names));
+
+        assert_eq!(target, vec![-0.3, 0.0, -0.2]);
+
+        // Without symbols nothing can be matched against the list
+        let mut target = vec![-0.3, -0.3, -0.4];
+        borrow.block_new_shorts(&mut target, &current, None);
+        assert_eq!(target, vec![-0.3, -0.3, -0.4]);
+    }
+}
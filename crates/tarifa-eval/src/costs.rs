@@ -0,0 +1,591 @@
+//! Transaction cost models.
+//!
+//! A [`CostModel`] prices the trades made at a rebalance. Each model covers
+//! one cost component (commission, spread, market impact, ...), and the
+//! backtester reports the cost of every configured model separately so the
+//! drag of each component can be inspected on its own.
+//!
+//! Costs are expressed as a fraction of portfolio value, the same units as
+//! portfolio weights and returns.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::stats::{mean, std_dev};
+
+/// Default trailing window (in periods) for average daily volume and volatility.
+pub const DEFAULT_ESTIMATION_WINDOW: usize = 20;
+
+/// Per-asset market state at a rebalance, used to price trades.
+///
+/// Every slice is indexed like the weights being traded. Models should treat
+/// missing slices and non-finite entries as unknown.
+#[derive(Debug, Clone, Copy)]
+pub struct CostContext<'a> {
+    /// Portfolio value in currency units before trading
+    pub portfolio_value: f64,
+    /// Asset prices
+    pub prices: Option<&'a [f64]>,
+    /// Relative bid-ask spreads (fraction of mid price)
+    pub spreads: Option<&'a [f64]>,
+    /// Average daily volume in shares
+    pub adv: Option<&'a [f64]>,
+    /// Daily return volatility
+    pub volatility: Option<&'a [f64]>,
+}
+
+impl CostContext<'_> {
+    /// Create a context with only a portfolio value and no market data.
+    pub const fn new(portfolio_value: f64) -> Self {
+        Self {
+            portfolio_value,
+            prices: None,
+            spreads: None,
+            adv: None,
+            volatility: None,
+        }
+    }
+
+    /// Price of asset `i`, if known and positive.
+    pub fn price(&self, i: usize) -> Option<f64> {
+        lookup(self.prices, i).filter(|&p| p > 0.0)
+    }
+
+    /// Relative spread of asset `i`, if known.
+    pub fn spread(&self, i: usize) -> Option<f64> {
+        lookup(self.spreads, i).filter(|&s| s >= 0.0)
+    }
+
+    /// Average daily volume of asset `i`, if known and positive.
+    pub fn adv(&self, i: usize) -> Option<f64> {
+        lookup(self.adv, i).filter(|&v| v > 0.0)
+    }
+
+    /// Daily volatility of asset `i`, if known.
+    pub fn volatility(&self, i: usize) -> Option<f64> {
+        lookup(self.volatility, i).filter(|&v| v >= 0.0)
+    }
+}
+
+fn lookup(values: Option<&[f64]>, i: usize) -> Option<f64> {
+    values
+        .and_then(|v| v.get(i))
+        .copied()
+        .filter(|v| v.is_finite())
+}
+
+/// A single transaction cost component.
+///
+/// Implementations must be thread-safe so backtests can be run in parallel.
+pub trait CostModel: Send + Sync {
+    /// Cost of each trade as a fraction of portfolio value.
+    ///
+    /// `trades[i]` is the change in weight of asset `i`; the returned vector
+    /// has the same length and holds non-negative costs.
+    fn trade_costs(&self, trades: &[f64], context: &CostContext<'_>) -> Vec<f64>;
+
+    /// Name of the cost component, used as its key in cost breakdowns.
+    fn name(&self) -> &str;
+}
+
+/// Flat cost in basis points of traded notional.
+///
+/// When no other cost model is configured the backtester uses
+/// [`FixedBpsCost::per_turnover`] with `BacktestConfig::transaction_cost_bps`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FixedBpsCost {
+    /// Cost in basis points of traded notional
+    pub bps: f64,
+}
+
+impl FixedBpsCost {
+    /// Create a flat basis-point cost model.
+    pub const fn new(bps: f64) -> Self {
+        Self { bps }
+    }
+
+    /// Create a model charging `bps` per unit of one-way turnover.
+    ///
+    /// Turnover is half the traded notional (`Σ|trade| / 2`), so this charges
+    /// `bps / 2` on each trade.
+    pub fn per_turnover(bps: f64) -> Self {
+        Self { bps: bps / 2.0 }
+    }
+}
+
+impl CostModel for FixedBpsCost {
+    fn trade_costs(&self, trades: &[f64], _context: &CostContext<'_>) -> Vec<f64> {
+        trades
+            .iter()
+            .map(|t| finite_abs(*t) * self.bps / 10_000.0)
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "fixed_bps"
+    }
+}
+
+/// Broker commission charged per share traded.
+///
+/// Share counts are derived from the portfolio value and asset prices. Trades
+/// in assets without a known price are not charged.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PerShareCommission {
+    /// Commission per share in currency units
+    pub per_share: f64,
+    /// Minimum commission per order in currency units
+    pub min_per_order: f64,
+}
+
+impl PerShareCommission {
+    /// Create a per-share commission model without an order minimum.
+    pub const fn new(per_share: f64) -> Self {
+        Self {
+            per_share,
+            min_per_order: 0.0,
+        }
+    }
+
+    /// Set the minimum commission charged per order.
+    pub const fn with_min_per_order(mut self, min_per_order: f64) -> Self {
+        self.min_per_order = min_per_order;
+        self
+    }
+}
+
+impl CostModel for PerShareCommission {
+    fn trade_costs(&self, trades: &[f64], context: &CostContext<'_>) -> Vec<f64> {
+        let value = context.portfolio_value;
+
+        trades
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                let weight = finite_abs(*t);
+                match context.price(i) {
+                    Some(price) if weight > 0.0 && value > 0.0 => {
+                        let shares = weight * value / price;
+                        (shares * self.per_share).max(self.min_per_order) / value
+                    }
+                    _ => 0.0,
+                }
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "commission"
+    }
+}
+
+/// Cost of crossing half the bid-ask spread.
+///
+/// Spreads come from quote data when available, otherwise from the
+/// Corwin-Schultz high/low estimator (see [`CostInputs`]). Assets without a
+/// spread estimate are charged `fallback_bps` of their full spread.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HalfSpreadCost {
+    /// Full spread in basis points assumed when no estimate is available
+    pub fallback_bps: f64,
+}
+
+impl HalfSpreadCost {
+    /// Create a half-spread model with a fallback spread in basis points.
+    pub const fn new(fallback_bps: f64) -> Self {
+        Self { fallback_bps }
+    }
+}
+
+impl Default for HalfSpreadCost {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+impl CostModel for HalfSpreadCost {
+    fn trade_costs(&self, trades: &[f64], context: &CostContext<'_>) -> Vec<f64> {
+        trades
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                let spread = context.spread(i).unwrap_or(self.fallback_bps / 10_000.0);
+                finite_abs(*t) * spread / 2.0
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "spread"
+    }
+}
+
+/// Square-root market impact.
+///
+/// The price impact of trading `Q` shares is `coefficient * sigma * sqrt(Q / ADV)`
+/// as a fraction of price, where `sigma` is daily volatility and `ADV` is
+/// average daily volume. Trades in assets missing a price, volume or
+/// volatility estimate are not charged.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SquareRootImpact {
+    /// Impact coefficient (order of 1 in empirical studies)
+    pub coefficient: f64,
+}
+
+impl SquareRootImpact {
+    /// Create a square-root impact model.
+    pub const fn new(coefficient: f64) -> Self {
+        Self { coefficient }
+    }
+}
+
+impl Default for SquareRootImpact {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl CostModel for SquareRootImpact {
+    fn trade_costs(&self, trades: &[f64], context: &CostContext<'_>) -> Vec<f64> {
+        let value = context.portfolio_value;
+
+        trades
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                let weight = finite_abs(*t);
+                match (context.price(i), context.adv(i), context.volatility(i)) {
+                    (Some(price), Some(adv), Some(sigma)) if weight > 0.0 && value > 0.0 => {
+                        let shares = weight * value / price;
+                        weight * self.coefficient * sigma * (shares / adv).sqrt()
+                    }
+                    _ => 0.0,
+                }
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "market_impact"
+    }
+}
+
+/// Transaction costs incurred at a single rebalance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebalanceCost {
+    /// Rebalance date
+    pub date: NaiveDate,
+    /// One-way turnover of the rebalance
+    pub turnover: f64,
+    /// Cost of each component, keyed by cost model name
+    pub components: BTreeMap<String, f64>,
+    /// Total cost across components
+    pub total: f64,
+}
+
+/// Dates x assets market data used to build a [`CostContext`] at each period.
+///
+/// All matrices are indexed like the score and return panels passed to the
+/// backtester. Empty matrices mean the quantity is unavailable.
+#[derive(Debug, Clone, Default)]
+pub struct CostInputs {
+    /// Asset prices
+    pub prices: Vec<Vec<f64>>,
+    /// Relative bid-ask spreads
+    pub spreads: Vec<Vec<f64>>,
+    /// Trailing average daily volume in shares
+    pub adv: Vec<Vec<f64>>,
+    /// Trailing daily return volatility
+    pub volatility: Vec<Vec<f64>>,
+}
+
+impl CostInputs {
+    /// Create inputs from prices, estimating trailing volatility from them.
+    ///
+    /// # Arguments
+    ///
+    /// * `prices` - Asset prices (dates x assets)
+    /// * `window` - Trailing window for the volatility estimate
+    pub fn from_prices(prices: Vec<Vec<f64>>, window: usize) -> Self {
+        let returns: Vec<Vec<f64>> = (0..prices.len())
+            .map(|t| {
+                (0..prices[t].len())
+                    .map(|j| {
+                        let prev = t
+                            .checked_sub(1)
+                            .and_then(|p| prices[p].get(j))
+                            .copied()
+                            .unwrap_or(f64::NAN);
+                        let cur = prices[t][j];
+                        if prev.is_finite() && cur.is_finite() && prev > 0.0 {
+                            cur / prev - 1.0
+                        } else {
+                            f64::NAN
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            volatility: trailing(&returns, window, std_dev),
+            prices,
+            ..Default::default()
+        }
+    }
+
+    /// Add trailing average daily volume computed from share volumes.
+    pub fn with_volume(mut self, volume: &[Vec<f64>], window: usize) -> Self {
+        self.adv = trailing(volume, window, mean);
+        self
+    }
+
+    /// Add spreads estimated from daily highs and lows (Corwin-Schultz).
+    ///
+    /// The estimate at `t` uses the high/low ranges of `t - 1` and `t`, so it
+    /// never looks ahead of the rebalance date.
+    pub fn with_high_low(mut self, high: &[Vec<f64>], low: &[Vec<f64>]) -> Self {
+        self.spreads = (0..high.len().min(low.len()))
+            .map(|t| {
+                (0..high[t].len().min(low[t].len()))
+                    .map(|j| {
+                        let prev = t
+                            .checked_sub(1)
+                            .and_then(|p| Some((*high[p].get(j)?, *low[p].get(j)?)));
+                        prev.map_or(f64::NAN, |(h0, l0)| {
+                            corwin_schultz_spread(h0, l0, high[t][j], low[t][j])
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        self
+    }
+
+    /// Add quoted spreads computed from bid and ask prices.
+    ///
+    /// Takes precedence over any high/low estimate where a valid quote is
+    /// available; names with missing or crossed quotes keep their estimate.
+    pub fn with_quotes(mut self, bid: &[Vec<f64>], ask: &[Vec<f64>]) -> Self {
+        let n_periods = bid.len().min(ask.len());
+        self.spreads
+            .resize(n_periods.max(self.spreads.len()), Vec::new());
+        for (t, (b, a)) in bid.iter().zip(ask.iter()).enumerate() {
+            let row = &mut self.spreads[t];
+            let n_assets = b.len().min(a.len());
+            if row.len() < n_assets {
+                row.resize(n_assets, f64::NAN);
+            }
+            for (spread, (&b, &a)) in row.iter_mut().zip(b.iter().zip(a.iter())) {
+                let mid = (a + b) / 2.0;
+                if b.is_finite() && a.is_finite() && mid > 0.0 && a >= b {
+                    *spread = (a - b) / mid;
+                }
+            }
+        }
+        self
+    }
+
+    /// Cost context for period `t`.
+    pub fn context(&self, t: usize, portfolio_value: f64) -> CostContext<'_> {
+        CostContext {
+            portfolio_value,
+            prices: self.prices.get(t).map(Vec::as_slice),
+            spreads: self.spreads.get(t).map(Vec::as_slice),
+            adv: self.adv.get(t).map(Vec::as_slice),
+            volatility: self.volatility.get(t).map(Vec::as_slice),
+        }
+    }
+
+    /// Restrict the inputs to the periods `start..end`.
+    pub fn slice(&self, start: usize, end: usize) -> Self {
+        let rows = |m: &[Vec<f64>]| {
+            m.get(start.min(m.len())..end.min(m.len()))
+                .map_or_else(Vec::new, <[Vec<f64>]>::to_vec)
+        };
+
+        Self {
+            prices: rows(&self.prices),
+            spreads: rows(&self.spreads),
+            adv: rows(&self.adv),
+            volatility: rows(&self.volatility),
+        }
+    }
+}
+
+/// Corwin-Schultz (2012) bid-ask spread estimate from two consecutive days.
+///
+/// Returns the relative spread, floored at zero. Returns `NaN` if any price
+/// is missing or non-positive.
+pub fn corwin_schultz_spread(high_prev: f64, low_prev: f64, high: f64, low: f64) -> f64 {
+    let prices = [high_prev, low_prev, high, low];
+    if prices.iter().any(|p| !p.is_finite() || *p <= 0.0) {
+        return f64::NAN;
+    }
+
+    let k = 3.0 - 2.0 * std::f64::consts::SQRT_2;
+    let beta = (high_prev / low_prev).ln().powi(2) + (high / low).ln().powi(2);
+    let gamma = (high_prev.max(high) / low_prev.min(low)).ln().powi(2);
+    let alpha = ((2.0 * beta).sqrt() - beta.sqrt()) / k - (gamma / k).sqrt();
+
+    (2.0 * (alpha.exp() - 1.0) / (1.0 + alpha.exp())).max(0.0)
+}
+
+/// Apply a statistic over a trailing window of each column, skipping NaNs.
+fn trailing(values: &[Vec<f64>], window: usize, stat: fn(&[f64]) -> f64) -> Vec<Vec<f64>> {
+    let window = window.max(1);
+
+    (0..values.len())
+        .map(|t| {
+            let start = (t + 1).saturating_sub(window);
+            (0..values[t].len())
+                .map(|j| {
+                    let obs: Vec<f64> = values[start..=t]
+                        .iter()
+                        .filter_map(|row| row.get(j).copied())
+                        .filter(|v| v.is_finite())
+                        .collect();
+                    stat(&obs)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+const fn finite_abs(value: f64) -> f64 {
+    if value.is_finite() { value.abs() } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_bps() {
+        let model = FixedBpsCost::new(10.0);
+        let costs = model.trade_costs(&[0.5, -0.25, 0.0], &CostContext::new(1.0));
+
+        assert!((costs[0] - 0.0005).abs() < 1e-12);
+        assert!((costs[1] - 0.00025).abs() < 1e-12);
+        assert_eq!(costs[2], 0.0);
+
+        // Per-turnover: 10 bps on turnover (0.5 + 0.25) / 2
+        let costs =
+            FixedBpsCost::per_turnover(10.0).trade_costs(&[0.5, -0.25], &CostContext::new(1.0));
+        assert!((costs.iter().sum::<f64>() - 0.375 * 0.001).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_per_share_commission() {
+        let prices = [50.0, 100.0];
+        let context = CostContext {
+            prices: Some(&prices),
+            ..CostContext::new(1_000_000.0)
+        };
+
+        // 10% of $1M at $50 = 2000 shares at $0.005 = $10
+        let costs = PerShareCommission::new(0.005).trade_costs(&[0.1, 0.0], &context);
+        assert!((costs[0] - 10.0 / 1_000_000.0).abs() < 1e-15);
+        assert_eq!(costs[1], 0.0);
+
+        let costs = PerShareCommission::new(0.005)
+            .with_min_per_order(25.0)
+            .trade_costs(&[0.1, 0.0], &context);
+        assert!((costs[0] - 25.0 / 1_000_000.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_half_spread_fallback() {
+        let spreads = [0.002, f64::NAN];
+        let context = CostContext {
+            spreads: Some(&spreads),
+            ..CostContext::new(1.0)
+        };
+
+        let costs = HalfSpreadCost::new(10.0).trade_costs(&[1.0, 1.0], &context);
+        assert!((costs[0] - 0.001).abs() < 1e-12);
+        assert!((costs[1] - 0.0005).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_square_root_impact() {
+        let prices = [100.0];
+        let adv = [10_000.0];
+        let vol = [0.02];
+        let context = CostContext {
+            prices: Some(&prices),
+            adv: Some(&adv),
+            volatility: Some(&vol),
+            ..CostContext::new(1_000_000.0)
+        };
+
+        // 10% of $1M at $100 = 1000 shares = 10% of ADV
+        let costs = SquareRootImpact::new(1.0).trade_costs(&[0.1], &context);
+        let expected = 0.1 * 0.02 * 0.1_f64.sqrt();
+        assert!((costs[0] - expected).abs() < 1e-12);
+
+        // Impact grows faster than linearly in trade size
+        let larger = SquareRootImpact::new(1.0).trade_costs(&[0.4], &context);
+        assert!(larger[0] > 4.0 * costs[0]);
+
+        // Missing inputs are not charged
+        let costs = SquareRootImpact::new(1.0).trade_costs(&[0.1], &CostContext::new(1.0));
+        assert_eq!(costs[0], 0.0);
+    }
+
+    #[test]
+    fn test_corwin_schultz_spread() {
+        assert!(corwin_schultz_spread(101.0, 99.0, 101.5, 99.5) >= 0.0);
+        assert!(corwin_schultz_spread(101.0, 0.0, 101.5, 99.5).is_nan());
+        // Identical ranges with no overnight move imply a positive spread
+        let s = corwin_schultz_spread(101.0, 99.0, 101.0, 99.0);
+        assert!(s > 0.0 && s < 0.05);
+    }
+
+    #[test]
+    fn test_cost_inputs() {
+        let prices = vec![vec![100.0, 10.0], vec![101.0, 10.5], vec![99.0, 10.2]];
+        let volume = vec![
+            vec![1000.0, 50.0],
+            vec![3000.0, 150.0],
+            vec![2000.0, f64::NAN],
+        ];
+        let bid = vec![vec![99.9, 9.9]; 3];
+        let ask = vec![vec![100.1, 10.1]; 3];
+
+        let inputs = CostInputs::from_prices(prices, 20)
+            .with_volume(&volume, 2)
+            .with_quotes(&bid, &ask);
+
+        let context = inputs.context(2, 1.0);
+        assert_eq!(context.price(0), Some(99.0));
+        assert_eq!(context.adv(0), Some(2500.0));
+        assert_eq!(context.adv(1), Some(150.0));
+        assert!((context.spread(0).unwrap() - 0.002).abs() < 1e-12);
+        assert!(context.volatility(0).unwrap() > 0.0);
+        // Not enough returns for a volatility estimate on the first periods
+        assert!(inputs.context(1, 1.0).volatility(0).is_none());
+
+        let sliced = inputs.slice(1, 3);
+        assert_eq!(sliced.context(0, 1.0).price(0), Some(101.0));
+        assert!(CostInputs::default().context(0, 1.0).prices.is_none());
+    }
+
+    #[test]
+    fn test_quotes_keep_high_low_where_missing() {
+        let high = vec![vec![101.0, 11.0]; 2];
+        let low = vec![vec![99.0, 9.0]; 2];
+        let bid = vec![vec![99.9, f64::NAN]; 2];
+        let ask = vec![vec![100.1, 10.1]; 2];
+
+        let estimated = CostInputs::default().with_high_low(&high, &low);
+        let inputs = estimated.clone().with_quotes(&bid, &ask);
+
+        let context = inputs.context(1, 1.0);
+        assert!((context.spread(0).unwrap() - 0.002).abs() < 1e-12);
+        assert_eq!(context.spread(1), estimated.context(1, 1.0).spread(1));
+        assert!(context.spread(1).is_some());
+    }
+}
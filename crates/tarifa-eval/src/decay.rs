@@ -2,9 +2,120 @@
 //!
 //! Analyzes how signal predictive power decays over different time horizons.
 //! Useful for determining optimal signal holding periods and rebalancing frequency.
+//! Exponential and power-law decay models can be fitted to a decay curve to
+//! get a half-life with a confidence interval even when the measured curve is
+//! noisy or non-monotone.
 
 use serde::{Deserialize, Serialize};
 
+use crate::metrics::{HacLag, newey_west_std_error};
+use crate::performance::inverse_normal_cdf;
+
+/// Parametric form of IC decay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DecayModel {
+    /// `IC(h) = a * exp(-rate * h)`
+    Exponential,
+    /// `IC(h) = a * h^(-rate)`
+    PowerLaw,
+}
+
+/// Decay model fitted to a decay curve.
+///
+/// The model is fitted by weighted least squares on `ln |IC|`, weighting each
+/// horizon by `(IC / std_error)^2`, the inverse variance of `ln |IC|` by the
+/// delta method. Parameter uncertainty takes the standard errors as known.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecayFit {
+    /// Fitted model
+    pub model: DecayModel,
+    /// Scale `a` (signed like the IC)
+    pub amplitude: f64,
+    /// Decay rate
+    pub rate: f64,
+    /// Standard error of the decay rate
+    pub rate_std_error: f64,
+    /// First horizon of the curve, from which the half-life is measured
+    pub base_horizon: f64,
+    /// Horizon at which the fitted IC is half its value at `base_horizon`
+    pub half_life: f64,
+    /// Lower bound of the half-life confidence interval
+    pub half_life_lower: f64,
+    /// Upper bound of the half-life confidence interval (infinite if the
+    /// rate is not significantly positive)
+    pub half_life_upper: f64,
+    /// Confidence level of the half-life interval
+    pub confidence: f64,
+    /// Weighted R-squared of the fitted IC values, with weights `1 / std_error^2`
+    pub r_squared: f64,
+    /// Chi-squared of the IC residuals divided by the degrees of freedom
+    pub reduced_chi_squared: f64,
+    /// Number of horizons used in the fit
+    pub n_points: usize,
+}
+
+impl DecayFit {
+    /// Fitted IC at a horizon.
+    pub fn ic_at(&self, horizon: f64) -> f64 {
+        match self.model {
+            DecayModel::Exponential => self.amplitude * (-self.rate * horizon).exp(),
+            DecayModel::PowerLaw => self.amplitude * horizon.powf(-self.rate),
+        }
+    }
+
+    /// Holding period that maximizes expected return net of turnover cost.
+    ///
+    /// Holding a fully refreshed portfolio for `H` periods earns the fitted
+    /// IC of each period it is held, `IC(1) + ... + IC(H)`, scaled by
+    /// `return_per_ic`, and pays `cost_per_rebalance` once. The period with
+    /// the highest net return per period is returned, or `None` if no holding
+    /// period up to `max_horizon` is profitable.
+    ///
+    /// # Arguments
+    ///
+    /// * `return_per_ic` - Expected return per period per unit of IC
+    /// * `cost_per_rebalance` - Cost of one full rebalance, in return units
+    /// * `max_horizon` - Longest holding period considered
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let fit = analysis.curve.fit(DecayModel::Exponential, 0.95).unwrap();
+    /// // 20bp round trip, 50bp return per unit IC per day
+    /// let holding = fit.optimal_holding_period(0.005, 0.002, 63);
+    /// ```
+    pub fn optimal_holding_period(
+        &self,
+        return_per_ic: f64,
+        cost_per_rebalance: f64,
+        max_horizon: usize,
+    ) -> Option<usize> {
+        let mut gross = 0.0;
+        let mut best: Option<(usize, f64)> = None;
+
+        for holding in 1..=max_horizon {
+            gross += self.ic_at(holding as f64).abs() * return_per_ic;
+            let net = (gross - cost_per_rebalance) / holding as f64;
+            if net > 0.0 && best.is_none_or(|(_, b)| net > b) {
+                best = Some((holding, net));
+            }
+        }
+
+        best.map(|(holding, _)| holding)
+    }
+
+    /// Half-life implied by a decay rate.
+    fn half_life_for(&self, rate: f64) -> f64 {
+        if rate <= 0.0 {
+            return f64::INFINITY;
+        }
+        match self.model {
+            DecayModel::Exponential => self.base_horizon + std::f64::consts::LN_2 / rate,
+            DecayModel::PowerLaw => self.base_horizon * 2.0_f64.powf(1.0 / rate),
+        }
+    }
+}
+
 /// Decay curve data points.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DecayCurve {
@@ -49,6 +160,119 @@ impl DecayCurve {
         None
     }
 
+    /// Fit a parametric decay model.
+    ///
+    /// Horizons whose IC is zero, non-finite or of the opposite sign to the
+    /// first horizon's IC, or whose standard error is not positive, are left
+    /// out. Returns `None` if fewer than three horizons remain.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - Decay model
+    /// * `confidence` - Confidence level of the half-life interval (e.g. 0.95)
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::DecayModel;
+    ///
+    /// if let Some(fit) = analysis.curve.fit(DecayModel::Exponential, 0.95) {
+    ///     println!(
+    ///         "half-life {:.1}d [{:.1}, {:.1}]",
+    ///         fit.half_life, fit.half_life_lower, fit.half_life_upper
+    ///     );
+    /// }
+    /// ```
+    pub fn fit(&self, model: DecayModel, confidence: f64) -> Option<DecayFit> {
+        let sign = self.ic_values.first()?.signum();
+        let points: Vec<(f64, f64, f64)> = self
+            .horizons
+            .iter()
+            .zip(&self.ic_values)
+            .zip(&self.ic_std_errors)
+            .map(|((&h, &ic), &se)| (h as f64, ic, se))
+            .filter(|&(h, ic, se)| {
+                ic.is_finite()
+                    && ic * sign > 0.0
+                    && se.is_finite()
+                    && se > 0.0
+                    && (model == DecayModel::Exponential || h > 0.0)
+            })
+            .collect();
+        let n_points = points.len();
+        if n_points < 3 {
+            return None;
+        }
+
+        // Weighted regression of ln|IC| on h (exponential) or ln h (power law)
+        let x = |h: f64| match model {
+            DecayModel::Exponential => h,
+            DecayModel::PowerLaw => h.ln(),
+        };
+        let (mut sw, mut swx, mut swxx, mut swy, mut swxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for &(h, ic, se) in &points {
+            let w = (ic / se).powi(2);
+            let (xi, yi) = (x(h), ic.abs().ln());
+            sw += w;
+            swx += w * xi;
+            swxx += w * xi * xi;
+            swy += w * yi;
+            swxy += w * xi * yi;
+        }
+        let det = sw * swxx - swx * swx;
+        if det <= 0.0 {
+            return None;
+        }
+        let slope = (sw * swxy - swx * swy) / det;
+        let intercept = (swy - slope * swx) / sw;
+        let rate_std_error = (sw / det).sqrt();
+
+        let base_horizon = self.horizons[0] as f64;
+        let mut fit = DecayFit {
+            model,
+            amplitude: sign * intercept.exp(),
+            rate: -slope,
+            rate_std_error,
+            base_horizon,
+            half_life: f64::NAN,
+            half_life_lower: f64::NAN,
+            half_life_upper: f64::NAN,
+            confidence,
+            r_squared: f64::NAN,
+            reduced_chi_squared: f64::NAN,
+            n_points,
+        };
+
+        let z = inverse_normal_cdf(0.5 + confidence / 2.0);
+        fit.half_life = fit.half_life_for(fit.rate);
+        fit.half_life_lower = fit.half_life_for(fit.rate + z * rate_std_error);
+        fit.half_life_upper = fit.half_life_for(fit.rate - z * rate_std_error);
+
+        let total_weight: f64 = points.iter().map(|p| p.2.powi(-2)).sum();
+        let mean_ic = points.iter().map(|p| p.1 * p.2.powi(-2)).sum::<f64>() / total_weight;
+        let (mut chi_squared, mut total) = (0.0, 0.0);
+        for &(h, ic, se) in &points {
+            chi_squared += ((ic - fit.ic_at(h)) / se).powi(2);
+            total += ((ic - mean_ic) / se).powi(2);
+        }
+        fit.r_squared = if total > 0.0 {
+            1.0 - chi_squared / total
+        } else {
+            f64::NAN
+        };
+        fit.reduced_chi_squared = chi_squared / (n_points - 2) as f64;
+
+        Some(fit)
+    }
+
+    /// Fit both decay models and return the one with the lower reduced chi-squared.
+    pub fn best_fit(&self, confidence: f64) -> Option<DecayFit> {
+        [DecayModel::Exponential, DecayModel::PowerLaw]
+            .into_iter()
+            .filter_map(|model| self.fit(model, confidence))
+            .min_by(|a, b| a.reduced_chi_squared.total_cmp(&b.reduced_chi_squared))
+    }
+
     /// Estimate half-life: horizon at which IC drops to 50% of initial value.
     pub fn half_life(&self) -> Option<f64> {
         if self.ic_values.is_empty() {
@@ -149,6 +373,42 @@ impl DecayAnalysis {
         }
     }
 
+    /// Analyze signal decay from the IC time series at each horizon.
+    ///
+    /// The IC at each horizon is the mean of its finite observations and its
+    /// standard error is Newey-West with `horizon - 1` lags, since ICs
+    /// measured every period over multi-period horizons overlap.
+    ///
+    /// # Arguments
+    ///
+    /// * `horizons` - Time horizons to analyze (in days)
+    /// * `ic_series` - Function that returns the IC time series for a given horizon
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::DecayAnalysis;
+    ///
+    /// let horizons = DecayAnalysis::standard_horizons();
+    /// let analysis = DecayAnalysis::from_ic_series(&horizons, |h| evaluator.ic_time_series(h));
+    /// ```
+    pub fn from_ic_series<F>(horizons: &[usize], mut ic_series: F) -> Self
+    where
+        F: FnMut(usize) -> Vec<f64>,
+    {
+        Self::analyze(horizons, |horizon| {
+            let series = ic_series(horizon);
+            let valid: Vec<f64> = series.iter().copied().filter(|x| x.is_finite()).collect();
+            let ic = if valid.is_empty() {
+                f64::NAN
+            } else {
+                valid.iter().sum::<f64>() / valid.len() as f64
+            };
+            let lags = HacLag::Horizon(horizon).lags(valid.len());
+            (ic, newey_west_std_error(&valid, lags))
+        })
+    }
+
     /// Standard horizons for decay analysis (1, 5, 10, 21, 42, 63 days).
     pub fn standard_horizons() -> Vec<usize> {
         vec![1, 5, 10, 21, 42, 63]
@@ -209,6 +469,80 @@ mod tests {
         assert!(hl > 0.0 && hl < 21.0);
     }
 
+    fn exponential_curve(noise: &[f64]) -> DecayCurve {
+        let horizons = vec![1, 5, 10, 21, 42, 63];
+        let ic_values = horizons
+            .iter()
+            .zip(noise)
+            .map(|(&h, n)| 0.08 * (-0.05 * h as f64).exp() * (1.0 + n))
+            .collect();
+        DecayCurve::new(horizons, ic_values, vec![0.005; 6])
+    }
+
+    #[test]
+    fn test_exponential_fit() {
+        let curve = exponential_curve(&[0.0; 6]);
+        let fit = curve.fit(DecayModel::Exponential, 0.95).unwrap();
+
+        assert!((fit.rate - 0.05).abs() < 1e-10);
+        assert!((fit.amplitude - 0.08).abs() < 1e-10);
+        assert!((fit.half_life - (1.0 + std::f64::consts::LN_2 / 0.05)).abs() < 1e-8);
+        assert!(fit.half_life_lower < fit.half_life && fit.half_life < fit.half_life_upper);
+        assert!((fit.r_squared - 1.0).abs() < 1e-10);
+        assert!(fit.reduced_chi_squared < 1e-10);
+        assert_eq!(fit.n_points, 6);
+    }
+
+    #[test]
+    fn test_fit_non_monotone_curve() {
+        // Noise makes the measured curve non-monotone
+        let curve = exponential_curve(&[0.0, 0.25, -0.15, 0.2, -0.1, 0.3]);
+        assert!(!curve.ic_values.windows(2).all(|w| w[0] >= w[1]));
+
+        let fit = curve.fit(DecayModel::Exponential, 0.95).unwrap();
+        assert!(fit.rate > 0.03 && fit.rate < 0.07);
+        assert!(fit.half_life.is_finite());
+        assert!(fit.r_squared > 0.8);
+    }
+
+    #[test]
+    fn test_power_law_fit() {
+        let horizons = vec![1, 2, 4, 8, 16];
+        let ic_values = horizons
+            .iter()
+            .map(|&h| -0.06 * (h as f64).powf(-0.5))
+            .collect();
+        let curve = DecayCurve::new(horizons, ic_values, vec![0.01; 5]);
+
+        let fit = curve.fit(DecayModel::PowerLaw, 0.95).unwrap();
+        assert!((fit.rate - 0.5).abs() < 1e-10);
+        assert!((fit.amplitude + 0.06).abs() < 1e-10);
+        // IC halves when the horizon quadruples
+        assert!((fit.half_life - 4.0).abs() < 1e-8);
+        assert_eq!(curve.best_fit(0.95).unwrap().model, DecayModel::PowerLaw);
+    }
+
+    #[test]
+    fn test_fit_insufficient_points() {
+        let curve = DecayCurve::new(vec![1, 5, 10], vec![0.05, -0.01, 0.02], vec![0.01; 3]);
+        assert!(curve.fit(DecayModel::Exponential, 0.95).is_none());
+    }
+
+    #[test]
+    fn test_optimal_holding_period() {
+        let fit = exponential_curve(&[0.0; 6])
+            .fit(DecayModel::Exponential, 0.95)
+            .unwrap();
+
+        // Free trading: rebalance every period to hold the freshest signal
+        assert_eq!(fit.optimal_holding_period(0.01, 0.0, 63), Some(1));
+        // Costs push the optimal holding period out
+        let holding = fit.optimal_holding_period(0.01, 0.002, 63).unwrap();
+        assert!(holding > 1 && holding < 63);
+        // Costs larger than any achievable alpha
+        assert_eq!(fit.optimal_holding_period(0.01, 1.0, 63), None);
+    }
+
     #[test]
     fn test_decay_analysis() {
         let horizons = vec![1, 5, 10, 21];
@@ -247,6 +581,26 @@ mod tests {
         assert_eq!(analysis.max_ic.0, 5);
     }
 
+    #[test]
+    fn test_from_ic_series_hac_errors() {
+        // Persistent IC series: a slow sine wave around 0.05
+        let series = |h: usize| -> Vec<f64> {
+            (0..200)
+                .map(|i| 0.05 / h as f64 + 0.02 * (i as f64 / 10.0).sin())
+                .collect()
+        };
+        let analysis = DecayAnalysis::from_ic_series(&[1, 21], series);
+
+        assert!(
+            (analysis.curve.ic_values[0] - series(1).iter().sum::<f64>() / 200.0).abs() < 1e-12
+        );
+        assert!(analysis.is_monotonic);
+        // Same dispersion, but the 21-day error accounts for autocorrelation
+        let naive = newey_west_std_error(&series(21), 0);
+        assert!((analysis.curve.ic_std_errors[0] - naive).abs() < 1e-12);
+        assert!(analysis.curve.ic_std_errors[1] > naive);
+    }
+
     #[test]
     fn test_standard_horizons() {
         let horizons = DecayAnalysis::standard_horizons();
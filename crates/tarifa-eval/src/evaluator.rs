@@ -5,6 +5,10 @@
 
 use ndarray::Array1;
 use serde::{Deserialize, Serialize};
+use tarifa_traits::PurgedKFold;
+
+use crate::ic::IcMethod;
+use crate::stats::finite_mean;
 
 /// Configuration for signal evaluator.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +23,9 @@ pub struct EvaluatorConfig {
     pub default_horizon: usize,
     /// Number of periods for rolling calculations
     pub rolling_window: usize,
+    /// Correlation measure used for the IC
+    #[serde(default)]
+    pub ic_method: IcMethod,
 }
 
 impl Default for EvaluatorConfig {
@@ -29,10 +36,95 @@ impl Default for EvaluatorConfig {
             annualize: true,
             default_horizon: 21,
             rolling_window: 63,
+            ic_method: IcMethod::Spearman,
         }
     }
 }
 
+/// IC of one purged cross-validation split.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CvFold {
+    /// Groups held out for testing
+    pub test_groups: Vec<usize>,
+    /// Mean IC over the purged training periods
+    pub train_ic: f64,
+    /// Mean IC over the test periods
+    pub test_ic: f64,
+    /// Number of finite training ICs
+    pub n_train: usize,
+    /// Number of finite test ICs
+    pub n_test: usize,
+}
+
+/// Purged cross-validation of a signal's IC.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossValidatedIc {
+    /// Forward return horizon used for the IC and for purging
+    pub horizon: usize,
+    /// Per-split results
+    pub folds: Vec<CvFold>,
+    /// Mean training IC across splits
+    pub mean_train_ic: f64,
+    /// Mean test IC across splits
+    pub mean_test_ic: f64,
+    /// Fraction of splits where train and test IC have the same sign
+    pub sign_agreement: f64,
+}
+
+/// IC of a signal within groups of assets over time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupIcBreakdown {
+    /// Forward return horizon
+    pub horizon: usize,
+    /// Group labels, sorted
+    pub groups: Vec<String>,
+    /// IC time series of each group, in the order of `groups`
+    pub group_ic: Vec<Vec<f64>>,
+    /// Mean IC of each group
+    pub mean_group_ic: Vec<f64>,
+    /// Pooled group-neutral IC time series
+    pub neutral_ic: Vec<f64>,
+    /// Mean group-neutral IC
+    pub mean_neutral_ic: f64,
+    /// Mean unadjusted IC, for comparison
+    pub mean_ic: f64,
+}
+
+/// Rolling signal metrics over `EvaluatorConfig::rolling_window` periods.
+///
+/// Entry `i` covers the window of IC periods ending at `i`; entries before
+/// the first full window are NaN, as are all entries for a zero window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollingMetrics {
+    /// Forward return horizon
+    pub horizon: usize,
+    /// Window length in periods
+    pub window: usize,
+    /// Rolling mean IC
+    pub ic: Vec<f64>,
+    /// Rolling Information Ratio (annualized if configured)
+    pub ir: Vec<f64>,
+    /// Rolling signal turnover
+    pub turnover: Vec<f64>,
+}
+
+/// IC statistics within one regime.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegimeIc {
+    /// Regime label
+    pub regime: String,
+    /// Mean IC
+    pub mean_ic: f64,
+    /// Information Ratio (annualized if configured)
+    pub ir: f64,
+    /// Newey-West t-statistic of the mean IC
+    pub t_stat: f64,
+    /// Fraction of positive ICs
+    pub hit_rate: f64,
+    /// Number of finite IC observations
+    pub n_obs: usize,
+}
+
 /// Default implementation of SignalEvaluator.
 ///
 /// This evaluator provides comprehensive signal analysis including:
@@ -46,6 +138,10 @@ pub struct DefaultEvaluator {
     signal_scores: Vec<Vec<f64>>,
     /// Forward returns over time
     forward_returns: Vec<Vec<f64>>,
+    /// Market capitalizations over time, for cap-weighted IC
+    market_caps: Option<Vec<Vec<f64>>>,
+    /// Custom per-asset IC weights over time
+    ic_weights: Option<Vec<Vec<f64>>>,
     /// Configuration
     config: EvaluatorConfig,
 }
@@ -78,10 +174,26 @@ impl DefaultEvaluator {
         Self {
             signal_scores,
             forward_returns,
+            market_caps: None,
+            ic_weights: None,
             config,
         }
     }
 
+    /// Set market capitalizations (dates x assets) for [`IcMethod::CapWeighted`].
+    ///
+    /// The IC at date `i` weights each asset by its capitalization at `i`.
+    pub fn with_market_caps(mut self, market_caps: Vec<Vec<f64>>) -> Self {
+        self.market_caps = Some(market_caps);
+        self
+    }
+
+    /// Set custom per-asset weights (dates x assets) for [`IcMethod::Weighted`].
+    pub fn with_ic_weights(mut self, weights: Vec<Vec<f64>>) -> Self {
+        self.ic_weights = Some(weights);
+        self
+    }
+
     /// Calculate Information Coefficient at a given horizon.
     ///
     /// # Arguments
@@ -123,6 +235,7 @@ impl DefaultEvaluator {
                 min_observations: self.config.min_observations,
                 annualize: self.config.annualize,
                 trading_days_per_year: self.config.trading_days_per_year,
+                hac_lag: crate::metrics::HacLag::Horizon(horizon),
                 ..Default::default()
             },
         );
@@ -136,20 +249,35 @@ impl DefaultEvaluator {
     ///
     /// Average turnover rate
     pub fn turnover(&self) -> f64 {
-        let rank_series = self.compute_rank_series();
-        let turnover = crate::metrics::SignalTurnover::calculate(
-            &rank_series,
-            &crate::metrics::MetricsConfig {
-                min_observations: self.config.min_observations,
-                ..Default::default()
-            },
-        );
+        signal_turnover(&self.signal_scores, self.config.min_observations)
+    }
 
-        turnover.turnover_rate
+    /// Position-level turnover and holding periods of quantile portfolios.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Quantiles, autocorrelation lags and rebalancing frequency
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::TurnoverConfig;
+    ///
+    /// let turnover = evaluator.position_turnover(&TurnoverConfig::default());
+    /// let cost = turnover.annual_cost(10.0);
+    /// ```
+    pub fn position_turnover(
+        &self,
+        config: &crate::metrics::TurnoverConfig,
+    ) -> crate::metrics::PositionTurnover {
+        crate::metrics::PositionTurnover::calculate(&self.compute_rank_series(), config)
     }
 
     /// Calculate IC time series at a given horizon.
     ///
+    /// The IC is measured with the configured [`IcMethod`]. Weighted methods
+    /// yield NaN for periods without market caps or weights.
+    ///
     /// # Arguments
     ///
     /// * `horizon` - Forward return horizon in days
@@ -170,7 +298,13 @@ impl DefaultEvaluator {
             if i + horizon < self.forward_returns.len() {
                 let scores = Array1::from_vec(self.signal_scores[i].clone());
                 let returns = Array1::from_vec(self.forward_returns[i + horizon].clone());
-                let ic = crate::ic::calculate_ic(&scores, &returns);
+                let weights = self.period_weights(i).map(|w| Array1::from_vec(w.to_vec()));
+                let ic = crate::ic::calculate_ic_with(
+                    &scores,
+                    &returns,
+                    self.config.ic_method,
+                    weights.as_ref(),
+                );
                 ic_series.push(ic);
             }
         }
@@ -178,8 +312,184 @@ impl DefaultEvaluator {
         ic_series
     }
 
+    /// Break the IC at a given horizon down by group.
+    ///
+    /// Comparing `mean_neutral_ic` with `mean_ic` shows how much of the
+    /// signal's IC comes from an implicit bet on groups rather than from
+    /// ranking assets within them.
+    ///
+    /// # Arguments
+    ///
+    /// * `horizon` - Forward return horizon in days
+    /// * `groups` - Group label of each asset (sector, industry, country, size bucket, ...)
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let by_sector = evaluator.group_ic(21, &sectors);
+    /// println!("IC {:.4}, sector-neutral {:.4}", by_sector.mean_ic, by_sector.mean_neutral_ic);
+    /// ```
+    pub fn group_ic(&self, horizon: usize, groups: &[String]) -> GroupIcBreakdown {
+        let n_periods = self
+            .signal_scores
+            .len()
+            .min(self.forward_returns.len())
+            .saturating_sub(horizon);
+
+        let mut labels: Vec<String> = groups.to_vec();
+        labels.sort();
+        labels.dedup();
+
+        let mut group_ic = vec![Vec::with_capacity(n_periods); labels.len()];
+        let mut neutral_ic = Vec::with_capacity(n_periods);
+
+        for i in 0..n_periods {
+            let scores = Array1::from_vec(self.signal_scores[i].clone());
+            let returns = Array1::from_vec(self.forward_returns[i + horizon].clone());
+            let weights = self.period_weights(i).map(|w| Array1::from_vec(w.to_vec()));
+            let period = crate::ic::group_ic(
+                &scores,
+                &returns,
+                groups,
+                self.config.ic_method,
+                weights.as_ref(),
+            );
+
+            for (g, label) in labels.iter().enumerate() {
+                let ic = period
+                    .groups
+                    .iter()
+                    .position(|p| p == label)
+                    .map_or(f64::NAN, |k| period.group_ic[k]);
+                group_ic[g].push(ic);
+            }
+            neutral_ic.push(period.neutral_ic);
+        }
+
+        GroupIcBreakdown {
+            horizon,
+            mean_group_ic: group_ic.iter().map(|ic| finite_mean(ic)).collect(),
+            mean_neutral_ic: finite_mean(&neutral_ic),
+            mean_ic: finite_mean(&self.ic_time_series(horizon)),
+            groups: labels,
+            group_ic,
+            neutral_ic,
+        }
+    }
+
+    /// Rolling IC, IR and turnover over the configured rolling window.
+    ///
+    /// # Arguments
+    ///
+    /// * `horizon` - Forward return horizon in days
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let rolling = evaluator.rolling(21);
+    /// let worst = rolling.ic.iter().copied().filter(|x| x.is_finite()).fold(f64::INFINITY, f64::min);
+    /// ```
+    pub fn rolling(&self, horizon: usize) -> RollingMetrics {
+        let window = self.config.rolling_window;
+        let ic_series = self.ic_time_series(horizon);
+        let rank_series = self.compute_rank_series();
+        let config = crate::metrics::MetricsConfig {
+            min_observations: self.config.min_observations.min(window),
+            annualize: self.config.annualize,
+            trading_days_per_year: self.config.trading_days_per_year,
+            hac_lag: crate::metrics::HacLag::Horizon(horizon),
+            ..Default::default()
+        };
+
+        let n = ic_series.len();
+        let (mut ic, mut ir, mut turnover) =
+            (vec![f64::NAN; n], vec![f64::NAN; n], vec![f64::NAN; n]);
+        for end in (window..=n).filter(|_| window > 0) {
+            let start = end - window;
+            let stats =
+                crate::metrics::InformationRatio::calculate(&ic_series[start..end], &config);
+            ic[end - 1] = stats.mean_ic;
+            ir[end - 1] = stats.ir;
+            turnover[end - 1] =
+                crate::metrics::SignalTurnover::calculate(&rank_series[start..end], &config)
+                    .turnover_rate;
+        }
+
+        RollingMetrics {
+            horizon,
+            window,
+            ic,
+            ir,
+            turnover,
+        }
+    }
+
+    /// IC statistics conditioned on a regime label per period.
+    ///
+    /// `regimes[i]` labels the period whose scores are dated `i`, for
+    /// example from [`MarketRegime::labels`](crate::regime::MarketRegime::labels).
+    /// Unlabelled periods are left out. Results are sorted by regime label.
+    ///
+    /// # Arguments
+    ///
+    /// * `horizon` - Forward return horizon in days
+    /// * `regimes` - Regime label of each period
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::MarketRegime;
+    ///
+    /// let labels = MarketRegime::Drawdown { threshold: 0.1 }.labels(&market_returns);
+    /// for regime in evaluator.regime_ic(21, &labels) {
+    ///     println!("{}: IC {:.4} (t = {:.2})", regime.regime, regime.mean_ic, regime.t_stat);
+    /// }
+    /// ```
+    pub fn regime_ic(&self, horizon: usize, regimes: &[Option<String>]) -> Vec<RegimeIc> {
+        let ic_series = self.ic_time_series(horizon);
+        let config = crate::metrics::MetricsConfig {
+            min_observations: self.config.min_observations,
+            annualize: self.config.annualize,
+            trading_days_per_year: self.config.trading_days_per_year,
+            hac_lag: crate::metrics::HacLag::Horizon(horizon),
+            ..Default::default()
+        };
+
+        let mut by_regime: std::collections::BTreeMap<&str, Vec<f64>> =
+            std::collections::BTreeMap::new();
+        for (ic, regime) in ic_series.iter().zip(regimes) {
+            if let Some(regime) = regime {
+                by_regime.entry(regime.as_str()).or_default().push(*ic);
+            }
+        }
+
+        by_regime
+            .into_iter()
+            .map(|(regime, ics)| {
+                let stats = crate::metrics::InformationRatio::calculate(&ics, &config);
+                let finite: Vec<f64> = ics.into_iter().filter(|x| x.is_finite()).collect();
+                let hit_rate = if finite.is_empty() {
+                    f64::NAN
+                } else {
+                    finite.iter().filter(|&&ic| ic > 0.0).count() as f64 / finite.len() as f64
+                };
+                RegimeIc {
+                    regime: regime.to_string(),
+                    mean_ic: stats.mean_ic,
+                    ir: stats.ir,
+                    t_stat: stats.t_stat,
+                    hit_rate,
+                    n_obs: finite.len(),
+                }
+            })
+            .collect()
+    }
+
     /// Calculate comprehensive signal metrics.
     ///
+    /// The mean IC t-statistic uses Newey-West standard errors with
+    /// `horizon - 1` lags.
+    ///
     /// # Arguments
     ///
     /// * `horizon` - Forward return horizon in days
@@ -198,6 +508,7 @@ impl DefaultEvaluator {
                 min_observations: self.config.min_observations,
                 annualize: self.config.annualize,
                 trading_days_per_year: self.config.trading_days_per_year,
+                hac_lag: crate::metrics::HacLag::Horizon(horizon),
                 ..Default::default()
             },
         )
@@ -205,6 +516,9 @@ impl DefaultEvaluator {
 
     /// Perform decay analysis.
     ///
+    /// IC standard errors are Newey-West with `horizon - 1` lags, so
+    /// overlapping forward returns do not shrink them.
+    ///
     /// # Returns
     ///
     /// DecayAnalysis with IC at multiple horizons
@@ -214,25 +528,86 @@ impl DefaultEvaluator {
         crate::decay::DecayAnalysis::analyze(&horizons, |h| {
             let ic = self.ic(h);
             let ic_series = self.ic_time_series(h);
-            let valid_ics: Vec<f64> = ic_series
-                .iter()
-                .copied()
-                .filter(|x| x.is_finite())
-                .collect();
-
-            let std_err = if valid_ics.len() > 1 {
-                let mean = valid_ics.iter().sum::<f64>() / valid_ics.len() as f64;
-                let variance = valid_ics.iter().map(|ic| (ic - mean).powi(2)).sum::<f64>()
-                    / (valid_ics.len() - 1) as f64;
-                variance.sqrt() / (valid_ics.len() as f64).sqrt()
-            } else {
-                f64::NAN
-            };
+            let lags = crate::metrics::HacLag::Horizon(h).lags(ic_series.len());
+            let std_err = crate::metrics::newey_west_std_error(&ic_series, lags);
 
             (ic, std_err)
         })
     }
 
+    /// Cross-validate the IC at a given horizon with purged splits.
+    ///
+    /// The splitter's horizon is replaced by `horizon`, so training periods
+    /// whose forward returns overlap a test period are always purged; its
+    /// embargo and grouping are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `horizon` - Forward return horizon in days
+    /// * `cv` - Purged K-fold splitter
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_traits::PurgedKFold;
+    ///
+    /// let cv = evaluator.cross_validate(21, PurgedKFold::new(6, 2).with_embargo(5));
+    /// println!("Out-of-fold IC: {:.4}", cv.mean_test_ic);
+    /// ```
+    pub fn cross_validate(&self, horizon: usize, cv: PurgedKFold) -> CrossValidatedIc {
+        let ic_series = self.ic_time_series(horizon);
+        let splits = cv.with_horizon(horizon).splits(ic_series.len());
+
+        let folds: Vec<CvFold> = splits
+            .into_iter()
+            .map(|split| {
+                let train = split.train_values(&ic_series);
+                let test = split.test_values(&ic_series);
+                CvFold {
+                    test_groups: split.test_groups,
+                    train_ic: finite_mean(&train),
+                    test_ic: finite_mean(&test),
+                    n_train: train.iter().filter(|x| x.is_finite()).count(),
+                    n_test: test.iter().filter(|x| x.is_finite()).count(),
+                }
+            })
+            .collect();
+
+        let mean_train_ic = finite_mean(&folds.iter().map(|f| f.train_ic).collect::<Vec<_>>());
+        let mean_test_ic = finite_mean(&folds.iter().map(|f| f.test_ic).collect::<Vec<_>>());
+        let comparable: Vec<&CvFold> = folds
+            .iter()
+            .filter(|f| f.train_ic.is_finite() && f.test_ic.is_finite())
+            .collect();
+        let sign_agreement = if comparable.is_empty() {
+            f64::NAN
+        } else {
+            comparable
+                .iter()
+                .filter(|f| f.train_ic.signum() == f.test_ic.signum())
+                .count() as f64
+                / comparable.len() as f64
+        };
+
+        CrossValidatedIc {
+            horizon,
+            folds,
+            mean_train_ic,
+            mean_test_ic,
+            sign_agreement,
+        }
+    }
+
+    /// IC weights at date `i` for the configured method.
+    fn period_weights(&self, i: usize) -> Option<&[f64]> {
+        let weights = match self.config.ic_method {
+            IcMethod::CapWeighted => self.market_caps.as_ref(),
+            IcMethod::Weighted => self.ic_weights.as_ref(),
+            _ => None,
+        };
+        weights.and_then(|w| w.get(i)).map(Vec::as_slice)
+    }
+
     /// Compute rank series for turnover calculation.
     fn compute_rank_series(&self) -> Vec<Vec<f64>> {
         self.signal_scores
@@ -242,6 +617,23 @@ impl DefaultEvaluator {
     }
 }
 
+/// Turnover rate of the ranks of a score series (dates x assets).
+pub(crate) fn signal_turnover(signal_scores: &[Vec<f64>], min_observations: usize) -> f64 {
+    let rank_series: Vec<Vec<f64>> = signal_scores
+        .iter()
+        .map(|scores| compute_ranks(scores))
+        .collect();
+    let turnover = crate::metrics::SignalTurnover::calculate(
+        &rank_series,
+        &crate::metrics::MetricsConfig {
+            min_observations,
+            ..Default::default()
+        },
+    );
+
+    turnover.turnover_rate
+}
+
 /// Compute ranks of values.
 fn compute_ranks(values: &[f64]) -> Vec<f64> {
     let n = values.len();
@@ -387,6 +779,194 @@ mod tests {
         assert_eq!(decay.curve.horizons.len(), decay.curve.ic_values.len());
     }
 
+    #[test]
+    fn test_cross_validate() {
+        let scores: Vec<Vec<f64>> = (0..60).map(|_| vec![1.0, 2.0, 3.0, 4.0]).collect();
+        let returns: Vec<Vec<f64>> = (0..60)
+            .map(|t| {
+                if t % 4 == 0 {
+                    vec![0.04, 0.03, 0.02, 0.01]
+                } else {
+                    vec![0.01, 0.02, 0.03, 0.04]
+                }
+            })
+            .collect();
+        let evaluator = DefaultEvaluator::new(scores, returns, EvaluatorConfig::default());
+
+        let cv = evaluator.cross_validate(5, PurgedKFold::new(5, 1).with_embargo(2));
+        assert_eq!(cv.horizon, 5);
+        assert_eq!(cv.folds.len(), 5);
+        assert!(cv.mean_train_ic > 0.0 && cv.mean_test_ic > 0.0);
+        assert_eq!(cv.sign_agreement, 1.0);
+
+        // 55 ICs in groups of 11; an interior fold loses 5 + 5 + 2 periods to purging
+        assert_eq!(cv.folds[2].n_test, 11);
+        assert_eq!(cv.folds[2].n_train, 55 - 11 - 12);
+    }
+
+    #[test]
+    fn test_ic_methods() {
+        let scores = vec![vec![1.0, 2.0, 3.0, 4.0, 5.0]; 3];
+        let returns = vec![vec![0.02, 0.01, 0.03, 0.04, 0.05]; 3];
+        let evaluator = |method| {
+            DefaultEvaluator::new(
+                scores.clone(),
+                returns.clone(),
+                EvaluatorConfig {
+                    ic_method: method,
+                    ..Default::default()
+                },
+            )
+        };
+
+        let spearman = evaluator(IcMethod::Spearman).ic_time_series(1);
+        let kendall = evaluator(IcMethod::Kendall).ic_time_series(1);
+        assert!((spearman[0] - 0.9).abs() < 1e-12);
+        assert!((kendall[0] - 0.8).abs() < 1e-12);
+
+        // Cap-weighted IC needs market caps
+        assert!(evaluator(IcMethod::CapWeighted).ic_time_series(1)[0].is_nan());
+        let caps = vec![vec![1.0, 1.0, 100.0, 500.0, 1000.0]; 3];
+        let cap_weighted = evaluator(IcMethod::CapWeighted)
+            .with_market_caps(caps.clone())
+            .ic_time_series(1);
+        assert!(cap_weighted[0] > spearman[0]);
+
+        let weighted = evaluator(IcMethod::Weighted)
+            .with_ic_weights(caps)
+            .ic_time_series(1);
+        assert_eq!(weighted, cap_weighted);
+    }
+
+    #[test]
+    fn test_group_ic() {
+        // Sector bet: tech outperforms energy, but the signal is inverted within sectors
+        let scores = vec![vec![1.0, 0.0, 11.0, 10.0]; 10];
+        let returns = vec![vec![0.00, 0.01, 0.05, 0.06]; 10];
+        let groups: Vec<String> = ["energy", "energy", "tech", "tech"]
+            .iter()
+            .map(|s| (*s).to_string())
+            .collect();
+        let evaluator = DefaultEvaluator::new(scores, returns, EvaluatorConfig::default());
+
+        let breakdown = evaluator.group_ic(1, &groups);
+        assert_eq!(breakdown.groups, ["energy", "tech"]);
+        assert_eq!(breakdown.group_ic[0].len(), 9);
+        assert_eq!(breakdown.neutral_ic.len(), 9);
+        assert!(breakdown.mean_ic > 0.5);
+        assert!(breakdown.mean_neutral_ic < -0.99);
+        assert!(breakdown.mean_group_ic.iter().all(|&ic| ic < -0.99));
+    }
+
+    #[test]
+    fn test_position_turnover() {
+        // The top and bottom halves swap every two periods
+        let scores: Vec<Vec<f64>> = (0..20)
+            .map(|t| {
+                if (t / 2) % 2 == 0 {
+                    vec![1.0, 2.0, 3.0, 4.0]
+                } else {
+                    vec![3.0, 4.0, 1.0, 2.0]
+                }
+            })
+            .collect();
+        let evaluator = DefaultEvaluator::new(scores, Vec::new(), EvaluatorConfig::default());
+
+        let config = crate::metrics::TurnoverConfig {
+            n_quantiles: 2,
+            lags: vec![1, 4],
+            periods_per_year: 252,
+        };
+        let turnover = evaluator.position_turnover(&config);
+
+        assert_eq!(turnover.n_obs, 20);
+        assert!((turnover.rank_autocorr[1] - 1.0).abs() < 1e-12);
+        assert!(turnover.rank_autocorr[0] < 1.0);
+        assert!((turnover.holding_period[1] - 2.0).abs() < 1e-12);
+        assert!((turnover.top_turnover - 9.0 / 19.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rolling_metrics() {
+        let scores: Vec<Vec<f64>> = (0..40).map(|_| vec![1.0, 2.0, 3.0, 4.0]).collect();
+        let returns: Vec<Vec<f64>> = (0..40)
+            .map(|t| {
+                if t < 20 || t % 3 == 0 {
+                    vec![0.01, 0.02, 0.03, 0.04]
+                } else {
+                    vec![0.04, 0.03, 0.02, 0.01]
+                }
+            })
+            .collect();
+        let config = EvaluatorConfig {
+            rolling_window: 10,
+            min_observations: 5,
+            annualize: false,
+            ..Default::default()
+        };
+        let zero_window = DefaultEvaluator::new(
+            scores.clone(),
+            returns.clone(),
+            EvaluatorConfig {
+                rolling_window: 0,
+                ..config
+            },
+        );
+        let evaluator = DefaultEvaluator::new(scores, returns, config);
+
+        let rolling = zero_window.rolling(1);
+        assert_eq!(rolling.window, 0);
+        assert!(rolling.ic.iter().chain(&rolling.ir).all(|x| x.is_nan()));
+
+        let rolling = evaluator.rolling(1);
+        assert_eq!(rolling.window, 10);
+        assert_eq!(rolling.ic.len(), 39);
+        assert!(rolling.ic[..9].iter().all(|x| x.is_nan()));
+        // The first windows see only a perfect signal, later ones mostly an inverted one
+        assert!((rolling.ic[9] - 1.0).abs() < 1e-12);
+        assert!(rolling.ic[38] < 0.0);
+        assert!(rolling.ir[38].is_finite());
+        // Constant scores never turn over
+        assert!(rolling.turnover[9].is_nan() || rolling.turnover[9].abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_regime_ic() {
+        let scores: Vec<Vec<f64>> = (0..30).map(|_| vec![1.0, 2.0, 3.0, 4.0]).collect();
+        let returns: Vec<Vec<f64>> = (0..30)
+            .map(|t| {
+                if t % 2 == 0 {
+                    vec![0.01, 0.02, 0.03, 0.04]
+                } else {
+                    vec![0.04, 0.01, 0.03, 0.02]
+                }
+            })
+            .collect();
+        // IC at period i uses returns from period i + 1
+        let regimes: Vec<Option<String>> = (0..30)
+            .map(|t| match t {
+                0 => None,
+                t if t % 2 == 1 => Some("calm".to_string()),
+                _ => Some("stress".to_string()),
+            })
+            .collect();
+        let config = EvaluatorConfig {
+            min_observations: 2,
+            ..Default::default()
+        };
+        let evaluator = DefaultEvaluator::new(scores, returns, config);
+
+        let by_regime = evaluator.regime_ic(1, &regimes);
+        assert_eq!(by_regime.len(), 2);
+        assert_eq!(by_regime[0].regime, "calm");
+        assert_eq!(by_regime[0].n_obs, 14);
+        assert!((by_regime[0].mean_ic - 1.0).abs() < 1e-12);
+        assert_eq!(by_regime[0].hit_rate, 1.0);
+        assert_eq!(by_regime[1].regime, "stress");
+        assert_eq!(by_regime[1].n_obs, 14);
+        assert!(by_regime[1].mean_ic < 0.0);
+    }
+
     #[test]
     fn test_compute_ranks() {
         let values = vec![3.0, 1.0, 2.0, 4.0];
@@ -401,5 +981,15 @@ mod tests {
         assert_eq!(config.trading_days_per_year, 252);
         assert_eq!(config.default_horizon, 21);
         assert!(config.annualize);
+        assert_eq!(config.ic_method, IcMethod::Spearman);
+    }
+
+    #[test]
+    fn test_evaluator_config_without_ic_method() {
+        let json = r#"{"min_observations":10,"trading_days_per_year":252,"annualize":false,
+            "default_horizon":5,"rolling_window":21}"#;
+        let config: EvaluatorConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.min_observations, 10);
+        assert_eq!(config.ic_method, IcMethod::Spearman);
     }
 }
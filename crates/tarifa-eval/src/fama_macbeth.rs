@@ -0,0 +1,448 @@
+//! Fama-MacBeth factor return regressions.
+//!
+//! Each period, forward returns are regressed cross-sectionally on the
+//! signal's scores, optionally alongside control variables (other signals,
+//! size) and group dummies (sectors). The slope on the signal is that
+//! period's factor return; its time-series average is the factor premium,
+//! tested with Fama-MacBeth and Newey-West t-statistics.
+//!
+//! Inputs follow [`ic_series`](crate::ic::ic_series): `forward_returns[i]` is
+//! the return that follows `signal_scores[i]`.
+
+use std::collections::BTreeSet;
+
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+use tarifa_traits::{Result, TarifaError};
+
+use crate::linalg::least_squares;
+use crate::metrics::{HacLag, t_stat};
+use crate::stats::finite_mean;
+
+/// Fama-MacBeth configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FamaMacBethConfig {
+    /// Z-score the signal and controls cross-sectionally each period, so
+    /// premiums are returns per one standard deviation of exposure
+    pub standardize: bool,
+    /// Minimum number of assets for a period's regression
+    pub min_assets: usize,
+    /// Lag selection for the Newey-West t-statistics
+    pub hac_lag: HacLag,
+}
+
+impl Default for FamaMacBethConfig {
+    fn default() -> Self {
+        Self {
+            standardize: true,
+            min_assets: 10,
+            hac_lag: HacLag::Automatic,
+        }
+    }
+}
+
+/// Time series and significance of one regressor's premium.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactorPremium {
+    /// Regressor name
+    pub name: String,
+    /// Slope per period, NaN where the regression could not be run
+    pub returns: Vec<f64>,
+    /// Average slope over the finite periods
+    pub mean: f64,
+    /// Fama-MacBeth t-statistic, `mean / (std / sqrt(n))`
+    pub t_stat: f64,
+    /// t-statistic with a Newey-West standard error
+    pub newey_west_t_stat: f64,
+}
+
+/// Fama-MacBeth regression results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FamaMacBethResult {
+    /// Premium of the signal
+    pub signal: FactorPremium,
+    /// Premiums of the controls, in the order they were added
+    pub controls: Vec<FactorPremium>,
+    /// R-squared of each period's regression
+    pub r_squared: Vec<f64>,
+    /// Average R-squared over the finite periods
+    pub mean_r_squared: f64,
+    /// Number of periods with a regression
+    pub n_periods: usize,
+    /// Number of lags used for the Newey-West t-statistics
+    pub hac_lags: usize,
+}
+
+/// Cross-sectional regression driver.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use tarifa_eval::{FamaMacBeth, FamaMacBethConfig};
+///
+/// let result = FamaMacBeth::new(FamaMacBethConfig::default())
+///     .with_control("momentum", momentum_scores)
+///     .with_size(&market_caps)
+///     .with_groups(sectors)
+///     .run(&value_scores, &forward_returns)?;
+/// println!(
+///     "Premium {:.4} (t = {:.2}, NW t = {:.2})",
+///     result.signal.mean, result.signal.t_stat, result.signal.newey_west_t_stat
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FamaMacBeth {
+    config: FamaMacBethConfig,
+    controls: Vec<(String, Vec<Array1<f64>>)>,
+    groups: Option<Vec<String>>,
+}
+
+impl FamaMacBeth {
+    /// Create a regression driver without controls.
+    pub const fn new(config: FamaMacBethConfig) -> Self {
+        Self {
+            config,
+            controls: Vec::new(),
+            groups: None,
+        }
+    }
+
+    /// Add a control variable (periods x assets), such as another signal.
+    pub fn with_control(mut self, name: impl Into<String>, values: Vec<Array1<f64>>) -> Self {
+        self.controls.push((name.into(), values));
+        self
+    }
+
+    /// Add log market capitalization (periods x assets) as a `size` control.
+    ///
+    /// Non-positive capitalizations are treated as missing.
+    pub fn with_size(self, market_caps: &[Array1<f64>]) -> Self {
+        let log_caps = market_caps
+            .iter()
+            .map(|caps| caps.mapv(|c| if c > 0.0 { c.ln() } else { f64::NAN }))
+            .collect();
+        self.with_control("size", log_caps)
+    }
+
+    /// Add dummies for the group label of each asset (sector, industry, ...).
+    ///
+    /// One group per period is absorbed by the intercept. Group dummies are
+    /// not reported as premiums.
+    pub fn with_groups(mut self, groups: Vec<String>) -> Self {
+        self.groups = Some(groups);
+        self
+    }
+
+    /// Run one cross-sectional regression per period.
+    ///
+    /// Each period uses the assets with finite values for the return, the
+    /// signal and every control. Periods with fewer than `min_assets` such
+    /// assets, or too few to fit every coefficient, yield NaN slopes.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal_scores` - Signal scores (periods x assets)
+    /// * `forward_returns` - Forward returns (periods x assets)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the controls do not cover every period, the
+    /// group labels do not match the number of assets, or no period has
+    /// enough assets for a regression.
+    pub fn run(
+        &self,
+        signal_scores: &[Array1<f64>],
+        forward_returns: &[Array1<f64>],
+    ) -> Result<FamaMacBethResult> {
+        let n_periods = signal_scores.len().min(forward_returns.len());
+        for (name, values) in &self.controls {
+            if values.len() < n_periods {
+                return Err(TarifaError::InvalidData(format!(
+                    "control '{name}' covers {} periods, expected {n_periods}",
+                    values.len()
+                )));
+            }
+        }
+
+        let n_coefficients = 1 + self.controls.len();
+        let mut slopes = vec![Vec::with_capacity(n_periods); n_coefficients];
+        let mut r_squared = Vec::with_capacity(n_periods);
+
+        for i in 0..n_periods {
+            let fit = self.regress_period(i, &signal_scores[i], &forward_returns[i])?;
+            match fit {
+                Some((coefficients, r2)) => {
+                    for (k, series) in slopes.iter_mut().enumerate() {
+                        series.push(coefficients[k]);
+                    }
+                    r_squared.push(r2);
+                }
+                None => {
+                    for series in &mut slopes {
+                        series.push(f64::NAN);
+                    }
+                    r_squared.push(f64::NAN);
+                }
+            }
+        }
+
+        let n_regressions = slopes[0].iter().filter(|b| b.is_finite()).count();
+        if n_regressions == 0 {
+            return Err(TarifaError::InsufficientData(format!(
+                "no period has at least {} assets with complete data",
+                self.config.min_assets.max(n_coefficients + 1)
+            )));
+        }
+
+        let hac_lags = self.config.hac_lag.lags(n_regressions);
+        let mut controls: Vec<FactorPremium> = slopes
+            .into_iter()
+            .enumerate()
+            .map(|(k, returns)| {
+                let name = if k == 0 {
+                    "signal".to_string()
+                } else {
+                    self.controls[k - 1].0.clone()
+                };
+                premium(name, returns, hac_lags)
+            })
+            .collect();
+        let signal = controls.remove(0);
+
+        Ok(FamaMacBethResult {
+            signal,
+            controls,
+            mean_r_squared: finite_mean(&r_squared),
+            r_squared,
+            n_periods: n_regressions,
+            hac_lags,
+        })
+    }
+
+    /// Regress one period. Returns the signal and control slopes followed by
+    /// the R-squared, or `None` if the period has too few assets.
+    fn regress_period(
+        &self,
+        i: usize,
+        scores: &Array1<f64>,
+        returns: &Array1<f64>,
+    ) -> Result<Option<(Vec<f64>, f64)>> {
+        let n_assets = scores.len().min(returns.len());
+        if let Some(groups) = &self.groups
+            && groups.len() != n_assets
+        {
+            return Err(TarifaError::InvalidData(format!(
+                "{} group labels for {n_assets} assets in period {i}",
+                groups.len()
+            )));
+        }
+
+        let value = |values: &Array1<f64>, j: usize| values.get(j).copied().unwrap_or(f64::NAN);
+        let rows: Vec<usize> = (0..n_assets)
+            .filter(|&j| {
+                returns[j].is_finite()
+                    && scores[j].is_finite()
+                    && self
+                        .controls
+                        .iter()
+                        .all(|(_, values)| value(&values[i], j).is_finite())
+            })
+            .collect();
+
+        let mut columns: Vec<Vec<f64>> = vec![rows.iter().map(|&j| scores[j]).collect()];
+        for (_, values) in &self.controls {
+            columns.push(rows.iter().map(|&j| value(&values[i], j)).collect());
+        }
+        if self.config.standardize {
+            columns.iter_mut().for_each(|c| standardize(c));
+        }
+
+        if let Some(groups) = &self.groups {
+            let present: BTreeSet<&str> = rows.iter().map(|&j| groups[j].as_str()).collect();
+            for label in present.into_iter().skip(1) {
+                columns.push(
+                    rows.iter()
+                        .map(|&j| f64::from(groups[j] == label))
+                        .collect(),
+                );
+            }
+        }
+
+        let n_params = columns.len() + 1;
+        if rows.len() < self.config.min_assets.max(n_params + 1) {
+            return Ok(None);
+        }
+
+        let y: Vec<f64> = rows.iter().map(|&j| returns[j]).collect();
+        let design: Vec<Vec<f64>> = (0..rows.len())
+            .map(|r| {
+                std::iter::once(1.0)
+                    .chain(columns.iter().map(|c| c[r]))
+                    .collect()
+            })
+            .collect();
+
+        let Some(beta) = least_squares(&design, &y) else {
+            return Ok(None);
+        };
+
+        let mean_y = y.iter().sum::<f64>() / y.len() as f64;
+        let (mut ssr, mut sst) = (0.0, 0.0);
+        for (x, &target) in design.iter().zip(&y) {
+            let fitted: f64 = x.iter().zip(&beta).map(|(a, b)| a * b).sum();
+            ssr += (target - fitted).powi(2);
+            sst += (target - mean_y).powi(2);
+        }
+        let r2 = if sst > 0.0 { 1.0 - ssr / sst } else { f64::NAN };
+
+        // Skip the intercept; group dummies follow the reported slopes
+        let slopes = beta[1..=1 + self.controls.len()].to_vec();
+        Ok(Some((slopes, r2)))
+    }
+}
+
+fn premium(name: String, returns: Vec<f64>, hac_lags: usize) -> FactorPremium {
+    FactorPremium {
+        name,
+        mean: finite_mean(&returns),
+        t_stat: t_stat(&returns, None),
+        newey_west_t_stat: t_stat(&returns, Some(hac_lags)),
+        returns,
+    }
+}
+
+/// Cross-sectional z-score; constant columns become zero.
+fn standardize(values: &mut [f64]) {
+    let n = values.len();
+    if n < 2 {
+        return;
+    }
+    let mean = values.iter().sum::<f64>() / n as f64;
+    let sd = (values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt();
+    for x in values.iter_mut() {
+        *x = if sd > 0.0 { (*x - mean) / sd } else { 0.0 };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    fn config() -> FamaMacBethConfig {
+        FamaMacBethConfig {
+            standardize: false,
+            min_assets: 5,
+            hac_lag: HacLag::Fixed(0),
+        }
+    }
+
+    type Panel = (Vec<Array1<f64>>, Vec<Array1<f64>>, Vec<Array1<f64>>);
+
+    /// Returns = premium * score + beta * control + noise.
+    fn panel(n_periods: usize, n_assets: usize, premium: f64, beta: f64) -> Panel {
+        let mut rng = SplitMix64::new(3);
+        let mut scores = Vec::new();
+        let mut controls = Vec::new();
+        let mut returns = Vec::new();
+        for _ in 0..n_periods {
+            let s = Array1::from_iter((0..n_assets).map(|_| rng.next_normal()));
+            let c = Array1::from_iter((0..n_assets).map(|_| rng.next_normal()));
+            let r = Array1::from_iter(
+                (0..n_assets).map(|j| premium * s[j] + beta * c[j] + 0.01 * rng.next_normal()),
+            );
+            scores.push(s);
+            controls.push(c);
+            returns.push(r);
+        }
+        (scores, controls, returns)
+    }
+
+    #[test]
+    fn test_recovers_premiums() {
+        let (scores, controls, returns) = panel(60, 50, 0.02, -0.01);
+        let result = FamaMacBeth::new(config())
+            .with_control("momentum", controls)
+            .run(&scores, &returns)
+            .unwrap();
+
+        assert_eq!(result.n_periods, 60);
+        assert_eq!(result.signal.returns.len(), 60);
+        assert!((result.signal.mean - 0.02).abs() < 1e-3);
+        assert!(result.signal.t_stat > 10.0);
+        assert!(result.signal.newey_west_t_stat.is_finite());
+        assert_eq!(result.controls[0].name, "momentum");
+        assert!((result.controls[0].mean + 0.01).abs() < 1e-3);
+        assert!(result.mean_r_squared > 0.5 && result.mean_r_squared <= 1.0);
+    }
+
+    #[test]
+    fn test_exact_fit() {
+        let scores = vec![Array1::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]); 3];
+        let returns: Vec<Array1<f64>> = scores.iter().map(|s| s.mapv(|x| 0.01 + 0.5 * x)).collect();
+        let result = FamaMacBeth::new(config()).run(&scores, &returns).unwrap();
+
+        assert!(
+            result
+                .signal
+                .returns
+                .iter()
+                .all(|b| (b - 0.5).abs() < 1e-10)
+        );
+        assert!(result.r_squared.iter().all(|r| (r - 1.0).abs() < 1e-10));
+    }
+
+    #[test]
+    fn test_sector_dummies_absorb_sector_returns() {
+        // Scores proxy the sector; within sectors they do not predict returns
+        let scores = vec![Array1::from_vec(vec![1.0, 1.1, 0.9, 3.0, 3.1, 2.9]); 2];
+        let returns = vec![Array1::from_vec(vec![0.0, -0.01, 0.01, 0.05, 0.04, 0.06]); 2];
+        let sectors: Vec<String> = ["a", "a", "a", "b", "b", "b"]
+            .iter()
+            .map(|s| (*s).to_string())
+            .collect();
+
+        let naive = FamaMacBeth::new(config()).run(&scores, &returns).unwrap();
+        let neutral = FamaMacBeth::new(config())
+            .with_groups(sectors)
+            .run(&scores, &returns)
+            .unwrap();
+
+        assert!(naive.signal.mean > 0.0);
+        assert!(neutral.signal.mean < 0.0);
+        assert!(neutral.controls.is_empty());
+    }
+
+    #[test]
+    fn test_size_control_and_standardization() {
+        let (scores, _, returns) = panel(20, 30, 0.02, 0.0);
+        let caps: Vec<Array1<f64>> = (0..20)
+            .map(|_| Array1::from_iter((0..30).map(|j| 1.0e9 * (j + 1) as f64)))
+            .collect();
+        let result = FamaMacBeth::new(FamaMacBethConfig::default())
+            .with_size(&caps)
+            .run(&scores, &returns)
+            .unwrap();
+
+        assert_eq!(result.controls[0].name, "size");
+        assert!(result.signal.mean > 0.0);
+        assert!(result.controls[0].mean.abs() < 0.005);
+    }
+
+    #[test]
+    fn test_thin_periods_and_errors() {
+        let (scores, controls, returns) = panel(3, 4, 0.02, 0.0);
+        let thin = FamaMacBeth::new(config()).run(&scores, &returns);
+        assert!(matches!(thin, Err(TarifaError::InsufficientData(_))));
+
+        let short = FamaMacBeth::new(config())
+            .with_control("x", controls[..1].to_vec())
+            .run(&scores, &returns);
+        assert!(matches!(short, Err(TarifaError::InvalidData(_))));
+
+        let labels = FamaMacBeth::new(config())
+            .with_groups(vec!["a".to_string()])
+            .run(&scores, &returns);
+        assert!(matches!(labels, Err(TarifaError::InvalidData(_))));
+    }
+}
@@ -1,9 +1,36 @@
 //! Information Coefficient (IC) calculations.
 //!
 //! IC measures the Spearman rank correlation between signal scores and forward returns.
-//! It is a key metric for evaluating signal predictive power.
+//! It is a key metric for evaluating signal predictive power. Pearson, Kendall
+//! tau and weighted rank correlations are available through [`IcMethod`].
+
+use std::collections::BTreeMap;
 
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+
+/// Correlation measure used for the Information Coefficient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum IcMethod {
+    /// Spearman rank correlation
+    #[default]
+    Spearman,
+    /// Pearson correlation of raw scores and returns
+    Pearson,
+    /// Kendall tau-b rank correlation
+    Kendall,
+    /// Rank correlation with each asset weighted by its market capitalization
+    CapWeighted,
+    /// Rank correlation with caller-supplied per-asset weights
+    Weighted,
+}
+
+impl IcMethod {
+    /// Whether the method needs per-asset weights.
+    pub const fn is_weighted(&self) -> bool {
+        matches!(self, Self::CapWeighted | Self::Weighted)
+    }
+}
 
 /// Calculate Information Coefficient between signal scores and future returns.
 ///
@@ -66,7 +93,81 @@ pub fn calculate_ic(signal_scores: &Array1<f64>, forward_returns: &Array1<f64>)
     let return_ranks = compute_ranks(&pairs.iter().map(|(_, r)| *r).collect::<Vec<_>>());
 
     // Calculate Spearman correlation
-    spearman_correlation(&signal_ranks, &return_ranks)
+    pearson_correlation(&signal_ranks, &return_ranks)
+}
+
+/// Calculate Information Coefficient with a chosen correlation measure.
+///
+/// Assets with a non-finite score or return are dropped. Weighted methods
+/// compute a weighted Pearson correlation of the score and return ranks,
+/// using `weights` (market capitalizations for [`IcMethod::CapWeighted`]);
+/// assets with a non-finite or negative weight are dropped, and the IC is
+/// NaN if no weights are given.
+///
+/// # Arguments
+///
+/// * `signal_scores` - Array of signal scores for assets
+/// * `forward_returns` - Array of forward returns for the same assets
+/// * `method` - Correlation measure
+/// * `weights` - Per-asset weights, required by weighted methods
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use tarifa_eval::{IcMethod, calculate_ic_with};
+///
+/// let kendall = calculate_ic_with(&scores, &returns, IcMethod::Kendall, None);
+/// let cap_weighted = calculate_ic_with(&scores, &returns, IcMethod::CapWeighted, Some(&caps));
+/// ```
+pub fn calculate_ic_with(
+    signal_scores: &Array1<f64>,
+    forward_returns: &Array1<f64>,
+    method: IcMethod,
+    weights: Option<&Array1<f64>>,
+) -> f64 {
+    if signal_scores.len() != forward_returns.len() {
+        return f64::NAN;
+    }
+
+    match method {
+        IcMethod::Spearman => calculate_ic(signal_scores, forward_returns),
+        IcMethod::Pearson | IcMethod::Kendall => {
+            let pairs: Vec<(f64, f64)> = signal_scores
+                .iter()
+                .zip(forward_returns.iter())
+                .filter(|(s, r)| s.is_finite() && r.is_finite())
+                .map(|(&s, &r)| (s, r))
+                .collect();
+            if pairs.len() < 2 {
+                return f64::NAN;
+            }
+            let (x, y): (Vec<f64>, Vec<f64>) = pairs.into_iter().unzip();
+            if method == IcMethod::Pearson {
+                pearson_correlation(&x, &y)
+            } else {
+                kendall_tau(&x, &y)
+            }
+        }
+        IcMethod::CapWeighted | IcMethod::Weighted => {
+            let Some(weights) = weights.filter(|w| w.len() == signal_scores.len()) else {
+                return f64::NAN;
+            };
+            let triples: Vec<(f64, f64, f64)> = signal_scores
+                .iter()
+                .zip(forward_returns.iter())
+                .zip(weights.iter())
+                .filter(|((s, r), w)| s.is_finite() && r.is_finite() && w.is_finite() && **w >= 0.0)
+                .map(|((&s, &r), &w)| (s, r, w))
+                .collect();
+            if triples.len() < 2 {
+                return f64::NAN;
+            }
+            let signal_ranks = compute_ranks(&triples.iter().map(|t| t.0).collect::<Vec<_>>());
+            let return_ranks = compute_ranks(&triples.iter().map(|t| t.1).collect::<Vec<_>>());
+            let w: Vec<f64> = triples.iter().map(|t| t.2).collect();
+            weighted_correlation(&signal_ranks, &return_ranks, &w)
+        }
+    }
 }
 
 /// Calculate IC time series over multiple periods.
@@ -109,8 +210,103 @@ pub fn ic_series(
     ics
 }
 
+/// IC of one period within groups of assets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupIc {
+    /// Group labels, sorted
+    pub groups: Vec<String>,
+    /// IC within each group, in the order of `groups`
+    pub group_ic: Vec<f64>,
+    /// IC of group-demeaned scores and returns, pooled across groups
+    pub neutral_ic: f64,
+}
+
+/// Calculate IC within groups of assets (sector, industry, country, ...).
+///
+/// Each group's IC uses only its own members. The group-neutral IC demeans
+/// scores and returns within each group and correlates the residuals across
+/// all assets, so it ignores any ability of the signal to pick groups. Assets
+/// with a non-finite score or return are dropped, and groups left with fewer
+/// than two assets get a NaN IC and are excluded from the pooled IC.
+///
+/// # Arguments
+///
+/// * `signal_scores` - Array of signal scores for assets
+/// * `forward_returns` - Array of forward returns for the same assets
+/// * `groups` - Group label of each asset
+/// * `method` - Correlation measure
+/// * `weights` - Per-asset weights, required by weighted methods
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use tarifa_eval::{IcMethod, group_ic};
+///
+/// let by_sector = group_ic(&scores, &returns, &sectors, IcMethod::Spearman, None);
+/// println!("Sector-neutral IC: {:.4}", by_sector.neutral_ic);
+/// ```
+pub fn group_ic(
+    signal_scores: &Array1<f64>,
+    forward_returns: &Array1<f64>,
+    groups: &[String],
+    method: IcMethod,
+    weights: Option<&Array1<f64>>,
+) -> GroupIc {
+    let n = signal_scores.len();
+    if forward_returns.len() != n || groups.len() != n {
+        return GroupIc {
+            groups: Vec::new(),
+            group_ic: Vec::new(),
+            neutral_ic: f64::NAN,
+        };
+    }
+
+    let mut members: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (j, label) in groups.iter().enumerate() {
+        members.entry(label.as_str()).or_default().push(j);
+    }
+
+    let mut group_ic = Vec::with_capacity(members.len());
+    let mut demeaned_scores = Array1::from_elem(n, f64::NAN);
+    let mut demeaned_returns = Array1::from_elem(n, f64::NAN);
+
+    for assets in members.values() {
+        let valid: Vec<usize> = assets
+            .iter()
+            .copied()
+            .filter(|&j| signal_scores[j].is_finite() && forward_returns[j].is_finite())
+            .collect();
+
+        let select = |values: &Array1<f64>| Array1::from_iter(valid.iter().map(|&j| values[j]));
+        let scores = select(signal_scores);
+        let returns = select(forward_returns);
+        let group_weights = weights.filter(|w| w.len() == n).map(select);
+        group_ic.push(calculate_ic_with(
+            &scores,
+            &returns,
+            method,
+            group_weights.as_ref(),
+        ));
+
+        if valid.len() >= 2 {
+            let (mean_score, mean_return) =
+                (scores.mean().unwrap_or(0.0), returns.mean().unwrap_or(0.0));
+            for &j in &valid {
+                demeaned_scores[j] = signal_scores[j] - mean_score;
+                demeaned_returns[j] = forward_returns[j] - mean_return;
+            }
+        }
+    }
+
+    GroupIc {
+        groups: members.keys().map(|g| (*g).to_string()).collect(),
+        group_ic,
+        neutral_ic: calculate_ic_with(&demeaned_scores, &demeaned_returns, method, weights),
+    }
+}
+
 /// Compute ranks of values (handling ties with average rank).
-fn compute_ranks(values: &[f64]) -> Vec<f64> {
+pub(crate) fn compute_ranks(values: &[f64]) -> Vec<f64> {
     let n = values.len();
     let mut indexed: Vec<(usize, f64)> = values.iter().enumerate().map(|(i, &v)| (i, v)).collect();
 
@@ -139,8 +335,65 @@ fn compute_ranks(values: &[f64]) -> Vec<f64> {
     ranks
 }
 
-/// Calculate Spearman correlation coefficient.
-fn spearman_correlation(ranks_x: &[f64], ranks_y: &[f64]) -> f64 {
+/// Kendall tau-b, which corrects for ties in either variable.
+fn kendall_tau(x: &[f64], y: &[f64]) -> f64 {
+    let sign = |a: f64, b: f64| match a.partial_cmp(&b) {
+        Some(std::cmp::Ordering::Greater) => 1i64,
+        Some(std::cmp::Ordering::Less) => -1,
+        _ => 0,
+    };
+
+    let n = x.len();
+    let (mut concordant, mut discordant) = (0i64, 0i64);
+    let (mut untied_x, mut untied_y) = (0i64, 0i64);
+
+    for i in 0..n {
+        for j in i + 1..n {
+            let (dx, dy) = (sign(x[i], x[j]), sign(y[i], y[j]));
+            untied_x += dx.abs();
+            untied_y += dy.abs();
+            match (dx * dy).signum() {
+                1 => concordant += 1,
+                -1 => discordant += 1,
+                _ => {}
+            }
+        }
+    }
+
+    if untied_x == 0 || untied_y == 0 {
+        return f64::NAN;
+    }
+    (concordant - discordant) as f64 / ((untied_x as f64) * (untied_y as f64)).sqrt()
+}
+
+/// Weighted Pearson correlation.
+fn weighted_correlation(x: &[f64], y: &[f64], weights: &[f64]) -> f64 {
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return f64::NAN;
+    }
+
+    let mean = |v: &[f64]| v.iter().zip(weights).map(|(a, w)| a * w).sum::<f64>() / total;
+    let (mean_x, mean_y) = (mean(x), mean(y));
+
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for ((&a, &b), &w) in x.iter().zip(y).zip(weights) {
+        let (dx, dy) = (a - mean_x, b - mean_y);
+        cov += w * dx * dy;
+        var_x += w * dx * dx;
+        var_y += w * dy * dy;
+    }
+
+    if var_x == 0.0 || var_y == 0.0 {
+        return f64::NAN;
+    }
+    cov / (var_x.sqrt() * var_y.sqrt())
+}
+
+/// Calculate Pearson correlation coefficient (Spearman when given ranks).
+///
+/// Inputs must already be filtered to finite pairs.
+pub(crate) fn pearson_correlation(ranks_x: &[f64], ranks_y: &[f64]) -> f64 {
     let n = ranks_x.len() as f64;
 
     if n < 2.0 {
@@ -227,6 +480,95 @@ mod tests {
         assert!(ics.iter().all(|&ic| ic >= -1.0 && ic <= 1.0));
     }
 
+    #[test]
+    fn test_pearson_vs_spearman() {
+        let scores = array![1.0, 2.0, 3.0, 4.0, 5.0];
+        let returns = array![0.001, 0.002, 0.004, 0.008, 0.5];
+
+        let spearman = calculate_ic_with(&scores, &returns, IcMethod::Spearman, None);
+        let pearson = calculate_ic_with(&scores, &returns, IcMethod::Pearson, None);
+        assert!((spearman - 1.0).abs() < 1e-10);
+        assert!(pearson > 0.0 && pearson < 0.9);
+    }
+
+    #[test]
+    fn test_kendall_tau() {
+        let scores = array![1.0, 2.0, 3.0, 4.0];
+        let returns = array![0.01, 0.03, 0.02, 0.04];
+        // 5 concordant and 1 discordant pair
+        let tau = calculate_ic_with(&scores, &returns, IcMethod::Kendall, None);
+        assert!((tau - 4.0 / 6.0).abs() < 1e-12);
+
+        // Ties: tau-b = (C - D) / sqrt(n1 * n2) = 5 / sqrt(5 * 6)
+        let tied = array![1.0, 1.0, 3.0, 4.0];
+        let tau = calculate_ic_with(
+            &tied,
+            &array![0.01, 0.02, 0.03, 0.04],
+            IcMethod::Kendall,
+            None,
+        );
+        assert!((tau - 5.0 / 30.0_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_weighted_ic() {
+        let scores = array![1.0, 2.0, 3.0, 4.0, 5.0];
+        let returns = array![0.02, 0.01, 0.03, 0.04, 0.05];
+
+        // Equal weights reproduce Spearman
+        let equal = array![1.0, 1.0, 1.0, 1.0, 1.0];
+        let spearman = calculate_ic(&scores, &returns);
+        let weighted = calculate_ic_with(&scores, &returns, IcMethod::Weighted, Some(&equal));
+        assert!((weighted - spearman).abs() < 1e-12);
+
+        // Mis-ranked small caps barely matter when mega caps rank correctly
+        let caps = array![1.0, 1.0, 100.0, 500.0, 1000.0];
+        let cap_weighted = calculate_ic_with(&scores, &returns, IcMethod::CapWeighted, Some(&caps));
+        assert!(cap_weighted > spearman);
+
+        assert!(calculate_ic_with(&scores, &returns, IcMethod::CapWeighted, None).is_nan());
+        assert!(IcMethod::CapWeighted.is_weighted());
+        assert!(!IcMethod::Kendall.is_weighted());
+    }
+
+    fn sectors(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[test]
+    fn test_group_ic_sector_bet() {
+        // The signal ranks sectors correctly but is inverted within each sector
+        let scores = array![1.0, 0.0, 11.0, 10.0];
+        let returns = array![0.00, 0.01, 0.05, 0.06];
+        let groups = sectors(&["energy", "energy", "tech", "tech"]);
+
+        let raw = calculate_ic(&scores, &returns);
+        let result = group_ic(&scores, &returns, &groups, IcMethod::Spearman, None);
+
+        assert!(raw > 0.5);
+        assert_eq!(result.groups, ["energy", "tech"]);
+        assert!(result.group_ic.iter().all(|&ic| (ic + 1.0).abs() < 1e-12));
+        assert!((result.neutral_ic + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_group_ic_small_groups() {
+        let scores = array![1.0, 2.0, 3.0, 4.0, f64::NAN];
+        let returns = array![0.01, 0.02, 0.03, 0.05, 0.01];
+        let groups = sectors(&["a", "a", "a", "b", "c"]);
+
+        let result = group_ic(&scores, &returns, &groups, IcMethod::Pearson, None);
+        assert_eq!(result.group_ic.len(), 3);
+        assert!((result.group_ic[0] - 1.0).abs() < 1e-12);
+        assert!(result.group_ic[1].is_nan());
+        assert!(result.group_ic[2].is_nan());
+        assert!((result.neutral_ic - 1.0).abs() < 1e-12);
+
+        let mismatched = group_ic(&scores, &returns, &groups[..2], IcMethod::Spearman, None);
+        assert!(mismatched.groups.is_empty());
+        assert!(mismatched.neutral_ic.is_nan());
+    }
+
     #[test]
     fn test_compute_ranks() {
         let values = vec![3.0, 1.0, 2.0, 5.0, 4.0];
@@ -0,0 +1,232 @@
+//! Trade and holdings ledger.
+//!
+//! Records what the backtester did at every rebalance: the target it aimed
+//! for, the book it started from, the trades it made, what those trades cost
+//! and what each position contributed until the next rebalance.
+
+use std::fs::File;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use tarifa_traits::{Result, Symbol, TarifaError};
+
+/// Per-asset record of a single rebalance.
+///
+/// Every vector is indexed like the weights of the backtest. Weights, trades,
+/// costs and P&L are fractions of portfolio value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebalanceRecord {
+    /// Rebalance date
+    pub date: NaiveDate,
+    /// Target weights from portfolio construction and position limits
+    pub target_weights: Vec<f64>,
+    /// Drifted weights held before trading
+    pub pre_trade_weights: Vec<f64>,
+    /// Executed trades (`post_trade_weights - pre_trade_weights`)
+    pub trades: Vec<f64>,
+    /// Weights held after trading
+    pub post_trade_weights: Vec<f64>,
+    /// Transaction cost of each trade, summed over cost components
+    pub costs: Vec<f64>,
+    /// Return contribution of each position until the next rebalance
+    pub pnl: Vec<f64>,
+}
+
+/// Rebalance-by-rebalance history of a backtest.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Ledger {
+    /// Asset symbols, when the backtest was run on symbol-keyed data
+    pub symbols: Option<Vec<Symbol>>,
+    /// One record per rebalance
+    pub rebalances: Vec<RebalanceRecord>,
+}
+
+impl Ledger {
+    /// Flatten the ledger into a long-format DataFrame.
+    ///
+    /// One row per rebalance and asset with columns `date`, `symbol`,
+    /// `target_weight`, `pre_trade_weight`, `trade`, `post_trade_weight`,
+    /// `cost` and `pnl`. Assets that were neither held nor targeted are
+    /// omitted. Without symbols, the asset's column index is used as its
+    /// symbol.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the DataFrame cannot be built.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let df = result.ledger.to_frame()?;
+    /// println!("{df}");
+    /// ```
+    pub fn to_frame(&self) -> Result<DataFrame> {
+        let mut dates = Vec::new();
+        let mut symbols = Vec::new();
+        let mut target = Vec::new();
+        let mut pre_trade = Vec::new();
+        let mut trades = Vec::new();
+        let mut post_trade = Vec::new();
+        let mut costs = Vec::new();
+        let mut pnl = Vec::new();
+
+        let value = |v: &[f64], j: usize| v.get(j).copied().unwrap_or(0.0);
+
+        for record in &self.rebalances {
+            for j in 0..record.target_weights.len() {
+                let (t, pre, post) = (
+                    value(&record.target_weights, j),
+                    value(&record.pre_trade_weights, j),
+                    value(&record.post_trade_weights, j),
+                );
+                if t == 0.0 && pre == 0.0 && post == 0.0 {
+                    continue;
+                }
+
+                dates.push(record.date);
+                symbols.push(self.symbol(j));
+                target.push(t);
+                pre_trade.push(pre);
+                trades.push(value(&record.trades, j));
+                post_trade.push(post);
+                costs.push(value(&record.costs, j));
+                pnl.push(value(&record.pnl, j));
+            }
+        }
+
+        let df = df! {
+            "date" => dates,
+            "symbol" => symbols,
+            "target_weight" => target,
+            "pre_trade_weight" => pre_trade,
+            "trade" => trades,
+            "post_trade_weight" => post_trade,
+            "cost" => costs,
+            "pnl" => pnl,
+        }?;
+
+        Ok(df)
+    }
+
+    /// Write the ledger to a CSV file. See [`Ledger::to_frame`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written.
+    pub fn write_csv(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut df = self.to_frame()?;
+        let file = create_file(path.as_ref())?;
+        CsvWriter::new(file).finish(&mut df)?;
+        Ok(())
+    }
+
+    /// Write the ledger to a Parquet file. See [`Ledger::to_frame`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written.
+    pub fn write_parquet(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut df = self.to_frame()?;
+        let file = create_file(path.as_ref())?;
+        ParquetWriter::new(file).finish(&mut df)?;
+        Ok(())
+    }
+
+    fn symbol(&self, j: usize) -> String {
+        self.symbols
+            .as_ref()
+            .and_then(|s| s.get(j))
+            .cloned()
+            .unwrap_or_else(|| j.to_string())
+    }
+}
+
+fn create_file(path: &Path) -> Result<File> {
+    File::create(path)
+        .map_err(|e| TarifaError::Other(format!("Cannot create {}: {e}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger() -> Ledger {
+        Ledger {
+            symbols: Some(vec![
+                "AAA".to_string(),
+                "BBB".to_string(),
+                "CCC".to_string(),
+            ]),
+            rebalances: vec![RebalanceRecord {
+                date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+                target_weights: vec![0.5, -0.5, 0.0],
+                pre_trade_weights: vec![0.4, 0.0, 0.0],
+                trades: vec![0.1, -0.5, 0.0],
+                post_trade_weights: vec![0.5, -0.5, 0.0],
+                costs: vec![0.0001, 0.0005, 0.0],
+                pnl: vec![0.01, -0.002, 0.0],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_to_frame() {
+        let df = ledger().to_frame().unwrap();
+
+        // CCC is neither held nor targeted
+        assert_eq!(df.height(), 2);
+        assert_eq!(df.width(), 8);
+
+        let symbols: Vec<&str> = df
+            .column("symbol")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(symbols, vec!["AAA", "BBB"]);
+
+        let trades: Vec<f64> = df
+            .column("trade")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(trades, vec![0.1, -0.5]);
+    }
+
+    #[test]
+    fn test_to_frame_without_symbols() {
+        let mut ledger = ledger();
+        ledger.symbols = None;
+        let df = ledger.to_frame().unwrap();
+
+        let first = df.column("symbol").unwrap().str().unwrap().get(0);
+        assert_eq!(first, Some("0"));
+    }
+
+    #[test]
+    fn test_write_csv_and_parquet() {
+        let dir = std::env::temp_dir();
+        let csv = dir.join(format!("tarifa_ledger_{}.csv", std::process::id()));
+        let parquet = dir.join(format!("tarifa_ledger_{}.parquet", std::process::id()));
+
+        ledger().write_csv(&csv).unwrap();
+        ledger().write_parquet(&parquet).unwrap();
+
+        let contents = std::fs::read_to_string(&csv).unwrap();
+        assert!(contents.starts_with("date,symbol,target_weight"));
+        assert_eq!(contents.lines().count(), 3);
+
+        let df = ParquetReader::new(File::open(&parquet).unwrap())
+            .finish()
+            .unwrap();
+        assert_eq!(df.height(), 2);
+
+        std::fs::remove_file(csv).unwrap();
+        std::fs::remove_file(parquet).unwrap();
+    }
+}
@@ -1,10 +1,30 @@
 //! Backtesting and signal evaluation for tarifa.
 //!
 //! This crate provides comprehensive tools for evaluating trading signals including:
-//! - Information Coefficient (IC) calculations
+//! - Information Coefficient (IC) calculations (Spearman, Pearson, Kendall, cap-weighted)
+//! - Group-neutral IC and per-group (sector, country, ...) IC breakdowns
+//! - Fama-MacBeth cross-sectional factor return regressions with controls
+//! - Signal redundancy analysis (rank and IC correlations, clustering, incremental IC)
 //! - Signal quality metrics (IR, turnover, etc.)
-//! - Signal decay analysis across time horizons
+//! - Quantile membership turnover, holding periods and implied trading costs
+//! - `FactorEvaluator` over market data, computing factors and forward returns itself
+//! - Newey-West (HAC) t-statistics for mean IC at overlapping horizons
+//! - Signal decay analysis across time horizons, with exponential and power-law fits
+//! - Rolling IC, IR and turnover and regime-conditional IC
 //! - Backtesting framework with transaction costs
+//! - Return-series performance statistics (Sortino, drawdowns, VaR, calendar tables)
+//! - Benchmark-relative performance (tracking error, beta, capture ratios)
+//! - Block bootstrap confidence intervals for Sharpe, mean IC and IR
+//! - Probabilistic and Deflated Sharpe ratios and multiple-testing corrections
+//! - Walk-forward validation with rolling or expanding train windows
+//! - Monte Carlo null distributions from shuffled or autocorrelated random signals
+//! - Quantile portfolio returns, top-minus-bottom spreads and monotonicity
+//! - Symbol-keyed panel alignment for changing universes
+//! - Pluggable portfolio construction strategies
+//! - Transaction cost models (commission, spread, market impact)
+//! - Short borrow fees and hard-to-borrow constraints
+//! - Calendar-aware rebalancing schedules
+//! - Trade and holdings ledger with DataFrame, CSV and Parquet export
 //!
 //! # Example
 //!
@@ -22,14 +42,73 @@
 //! ```
 
 pub mod backtest;
+pub mod benchmark;
+pub mod bootstrap;
+pub mod borrow;
+pub mod costs;
 pub mod decay;
 pub mod evaluator;
+pub mod fama_macbeth;
 pub mod ic;
+pub mod ledger;
+mod linalg;
+pub mod market_evaluator;
 pub mod metrics;
+pub mod monte_carlo;
+pub mod panel;
+pub mod performance;
+pub mod portfolio;
+pub mod quantile;
+pub mod redundancy;
+pub mod regime;
+mod rng;
+pub mod schedule;
+pub mod significance;
+mod stats;
+pub mod walk_forward;
 
 // Re-export main types
-pub use backtest::{Backtest, BacktestConfig, BacktestResult};
-pub use decay::{DecayAnalysis, DecayCurve};
-pub use evaluator::{DefaultEvaluator, EvaluatorConfig};
-pub use ic::{calculate_ic, ic_series};
-pub use metrics::{InformationRatio, MetricsConfig, SignalMetrics, SignalTurnover};
+pub use backtest::{
+    Backtest, BacktestConfig, BacktestResult, apply_position_limits, drift_weights,
+};
+pub use benchmark::BenchmarkMetrics;
+pub use bootstrap::{
+    BootstrapConfig, BootstrapMethod, ConfidenceInterval, information_ratio_ci, mean_ic_ci,
+    sharpe_ratio_ci,
+};
+pub use borrow::{BorrowRates, ShortBorrow};
+pub use costs::{
+    CostContext, CostInputs, CostModel, FixedBpsCost, HalfSpreadCost, PerShareCommission,
+    RebalanceCost, SquareRootImpact,
+};
+pub use decay::{DecayAnalysis, DecayCurve, DecayFit, DecayModel};
+pub use evaluator::{
+    CrossValidatedIc, CvFold, DefaultEvaluator, EvaluatorConfig, GroupIcBreakdown, RegimeIc,
+    RollingMetrics,
+};
+pub use fama_macbeth::{FactorPremium, FamaMacBeth, FamaMacBethConfig, FamaMacBethResult};
+pub use ic::{GroupIc, IcMethod, calculate_ic, calculate_ic_with, group_ic, ic_series};
+pub use ledger::{Ledger, RebalanceRecord};
+pub use market_evaluator::{FactorScores, MarketEvaluator};
+pub use metrics::{
+    HacLag, InformationRatio, MetricsConfig, PositionTurnover, SignalMetrics, SignalTurnover,
+    TurnoverConfig, newey_west_std_error, t_stat,
+};
+pub use monte_carlo::{MonteCarlo, MonteCarloConfig, NullDistribution, NullModel};
+pub use panel::{Panel, PeriodCoverage};
+pub use performance::{CalendarReturn, PerformanceStats};
+pub use portfolio::{
+    InverseVolatilityConstructor, PortfolioConstructor, PortfolioContext, QuantileConstructor,
+    RankLinearConstructor, ScoreProportionalConstructor, TopBottomConstructor, ZScoreConstructor,
+};
+pub use quantile::{QuantileAnalysis, QuantileConfig, QuantileReturns};
+pub use redundancy::{ClusterMerge, Linkage, RedundancyAnalysis};
+pub use regime::MarketRegime;
+pub use schedule::RebalanceSchedule;
+pub use significance::{
+    Correction, DeflatedSharpe, IcSignificance, adjust_p_values, probabilistic_sharpe_ratio,
+};
+pub use walk_forward::{
+    ICWeightedModel, WalkForward, WalkForwardConfig, WalkForwardModel, WalkForwardResult,
+    WalkForwardWindow, WindowResult, WindowScheme,
+};
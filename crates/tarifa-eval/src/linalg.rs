@@ -0,0 +1,80 @@
+//! Small dense linear algebra helpers.
+//!
+//! Cross-sectional regressions in this crate have a handful of regressors,
+//! so the normal equations are solved directly instead of pulling in a
+//! linear algebra dependency.
+
+/// Ordinary least squares via the normal equations.
+///
+/// Returns `None` if the design matrix is rank deficient.
+pub(crate) fn least_squares(design: &[Vec<f64>], y: &[f64]) -> Option<Vec<f64>> {
+    let p = design.first()?.len();
+    let mut xtx = vec![vec![0.0; p]; p];
+    let mut xty = vec![0.0; p];
+    for (x, &target) in design.iter().zip(y) {
+        for a in 0..p {
+            xty[a] += x[a] * target;
+            for b in 0..p {
+                xtx[a][b] += x[a] * x[b];
+            }
+        }
+    }
+    solve(xtx, xty)
+}
+
+/// Solve `a x = b` by Gaussian elimination with partial pivoting.
+pub(crate) fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    let scale = a
+        .iter()
+        .enumerate()
+        .map(|(i, row)| row[i].abs())
+        .fold(0.0, f64::max);
+
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&r, &s| a[r][col].abs().total_cmp(&a[s][col].abs()))?;
+        if a[pivot][col].abs() <= 1e-12 * scale.max(1.0) {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        let pivot_row = a[col].clone();
+        for row in col + 1..n {
+            let factor = a[row][col] / pivot_row[col];
+            for (x, p) in a[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= factor * p;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let x = solve(vec![vec![2.0, 1.0], vec![1.0, 3.0]], vec![3.0, 5.0]).unwrap();
+        assert!((x[0] - 0.8).abs() < 1e-12);
+        assert!((x[1] - 1.4).abs() < 1e-12);
+        assert!(solve(vec![vec![1.0, 2.0], vec![2.0, 4.0]], vec![1.0, 2.0]).is_none());
+    }
+
+    #[test]
+    fn test_least_squares() {
+        let design = vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]];
+        let beta = least_squares(&design, &[1.0, 3.0, 5.0]).unwrap();
+        assert!((beta[0] - 1.0).abs() < 1e-12);
+        assert!((beta[1] - 2.0).abs() < 1e-12);
+        assert!(least_squares(&[], &[]).is_none());
+    }
+}
@@ -0,0 +1,438 @@
+//! Factor evaluation from raw market data.
+//!
+//! [`MarketEvaluator`] implements [`FactorEvaluator`]: it computes a factor
+//! on every trading date of a date range, derives one-period returns from
+//! the `close` column and measures IC, IR and turnover with a
+//! [`DefaultEvaluator`].
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use factors::Factor;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use tarifa_traits::{FactorEvaluator, MarketData, Result, Symbol, TarifaError};
+
+use crate::evaluator::{DefaultEvaluator, EvaluatorConfig, signal_turnover};
+use crate::panel::{CLOSE_COLUMN, DATE_COLUMN, PanelKeys, SYMBOL_COLUMN, float_column};
+
+/// Factor scores computed by a [`MarketEvaluator`].
+///
+/// `scores[t][j]` is the score of `symbols[j]` on `dates[t]`, NaN where the
+/// factor has no value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactorScores {
+    /// Evaluation dates
+    pub dates: Vec<NaiveDate>,
+    /// Universe symbols (one per column)
+    pub symbols: Vec<Symbol>,
+    /// Factor scores (dates x symbols)
+    pub scores: Vec<Vec<f64>>,
+}
+
+/// Evaluates factors against market data over a date range and universe.
+///
+/// The factor is computed on each trading date in `[start, end]` from the
+/// rows dated on or before that date, so it cannot see later prices. As in
+/// [`DefaultEvaluator`], the IC at horizon `h` pairs the scores on date `t`
+/// with the close-to-close return from `t + h - 1` to `t + h` trading dates;
+/// only prices up to `end` are used, so the last `h` dates of the range have
+/// no IC.
+///
+/// Each `ic`, `ir` and `turnover` call computes the factor afresh. To
+/// evaluate several metrics from one computation, build the evaluator once
+/// with [`MarketEvaluator::evaluator`] and query it.
+#[derive(Debug, Clone)]
+pub struct MarketEvaluator {
+    /// Long-format market data with `symbol`, `date` and `close` columns
+    data: MarketData,
+    /// First evaluation date (inclusive)
+    start: NaiveDate,
+    /// Last evaluation date (inclusive)
+    end: NaiveDate,
+    /// Symbols to evaluate; all symbols in the data if `None`
+    universe: Option<Vec<Symbol>>,
+    /// Configuration of the underlying evaluator
+    config: EvaluatorConfig,
+}
+
+impl MarketEvaluator {
+    /// Create a new evaluator over all symbols in the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Market data with `symbol`, `date` and `close` columns, plus
+    ///   whatever the evaluated factors require (including their lookback)
+    /// * `start` - First evaluation date (inclusive)
+    /// * `end` - Last evaluation date (inclusive)
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::MarketEvaluator;
+    /// use tarifa_traits::FactorEvaluator;
+    ///
+    /// let evaluator = MarketEvaluator::new(market_data, start, end)
+    ///     .with_universe(vec!["AAPL".to_string(), "MSFT".to_string()]);
+    /// let ic = evaluator.ic(&ShortTermMomentum::default(), 5);
+    /// ```
+    pub fn new(data: MarketData, start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            data,
+            start,
+            end,
+            universe: None,
+            config: EvaluatorConfig::default(),
+        }
+    }
+
+    /// Restrict the evaluation to a set of symbols.
+    pub fn with_universe(mut self, symbols: Vec<Symbol>) -> Self {
+        self.universe = Some(symbols);
+        self
+    }
+
+    /// Set the configuration of the underlying evaluator.
+    pub const fn with_config(mut self, config: EvaluatorConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Compute the factor on each evaluation date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data lacks a required column, a universe
+    /// symbol is absent from the data, no trading date falls in the range, or
+    /// the factor fails to compute.
+    pub fn factor_scores(&self, factor: &dyn Factor) -> Result<FactorScores> {
+        let grid = self.grid()?;
+        let scores = self.compute_scores(&grid, factor)?;
+        Ok(FactorScores {
+            dates: grid.dates[grid.range].to_vec(),
+            symbols: grid.symbols,
+            scores,
+        })
+    }
+
+    /// Build a [`DefaultEvaluator`] for a factor.
+    ///
+    /// Row `k` of its returns is the close-to-close return ending on
+    /// evaluation date `k`, so every horizon, decay analysis and rolling
+    /// metric of the evaluator follows the [`DefaultEvaluator`] conventions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as
+    /// [`factor_scores`](Self::factor_scores).
+    pub fn evaluator(&self, factor: &dyn Factor) -> Result<DefaultEvaluator> {
+        let grid = self.grid()?;
+        let scores = self.compute_scores(&grid, factor)?;
+
+        let forward_returns = grid
+            .range
+            .map(|t| {
+                grid.closes[t]
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &cur)| {
+                        let prev = t.checked_sub(1).map_or(f64::NAN, |s| grid.closes[s][j]);
+                        if prev.is_finite() && cur.is_finite() && prev > 0.0 {
+                            cur / prev - 1.0
+                        } else {
+                            f64::NAN
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(DefaultEvaluator::new(
+            scores,
+            forward_returns,
+            self.config.clone(),
+        ))
+    }
+
+    /// Factor scores (evaluation dates x universe symbols).
+    fn compute_scores(&self, grid: &Grid, factor: &dyn Factor) -> Result<Vec<Vec<f64>>> {
+        let data = self.data.data().clone().lazy();
+        let index: HashMap<&str, usize> = grid
+            .symbols
+            .iter()
+            .enumerate()
+            .map(|(j, s)| (s.as_str(), j))
+            .collect();
+
+        let mut scores = Vec::with_capacity(grid.range.len());
+        for &date in &grid.dates[grid.range.clone()] {
+            let visible = data
+                .clone()
+                .filter(col(DATE_COLUMN).cast(DataType::Date).lt_eq(lit(date)));
+            let computed = factor
+                .compute(&visible, date)
+                .map_err(|e| TarifaError::SignalComputation(e.to_string()))?;
+
+            let symbols = computed
+                .column(SYMBOL_COLUMN)
+                .map_err(|_| TarifaError::MissingColumn(SYMBOL_COLUMN.to_string()))?
+                .as_materialized_series()
+                .str()?
+                .clone();
+            let values = float_column(&computed, factor.name())?;
+
+            let mut row = vec![f64::NAN; grid.symbols.len()];
+            for (symbol, value) in symbols.into_iter().zip(values) {
+                if let (Some(j), Some(v)) = (symbol.and_then(|s| index.get(s)), value) {
+                    row[*j] = v;
+                }
+            }
+            scores.push(row);
+        }
+
+        Ok(scores)
+    }
+
+    /// Trading dates, universe and close prices from the data.
+    fn grid(&self) -> Result<Grid> {
+        let df = self.data.data();
+        let keys = PanelKeys::from_frame(df)?;
+        let closes = keys.pivot(&float_column(df, CLOSE_COLUMN)?)?;
+
+        let (symbols, columns): (Vec<Symbol>, Vec<usize>) = match &self.universe {
+            Some(universe) => universe
+                .iter()
+                .map(|symbol| {
+                    keys.symbols
+                        .binary_search(symbol)
+                        .map(|j| (symbol.clone(), j))
+                        .map_err(|_| TarifaError::SymbolNotFound(symbol.clone()))
+                })
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .unzip(),
+            None => (keys.symbols.clone(), (0..keys.symbols.len()).collect()),
+        };
+        let closes = closes
+            .iter()
+            .map(|row| columns.iter().map(|&j| row[j]).collect())
+            .collect();
+
+        let first = keys.dates.partition_point(|d| *d < self.start);
+        let last = keys.dates.partition_point(|d| *d <= self.end);
+        if first >= last {
+            return Err(TarifaError::InsufficientData(format!(
+                "No trading dates between {} and {}",
+                self.start, self.end
+            )));
+        }
+
+        Ok(Grid {
+            dates: keys.dates,
+            symbols,
+            closes,
+            range: first..last,
+        })
+    }
+}
+
+/// Close prices on every trading date in the data, restricted to the universe.
+#[derive(Debug)]
+struct Grid {
+    dates: Vec<NaiveDate>,
+    symbols: Vec<Symbol>,
+    /// Close prices (all dates x universe symbols)
+    closes: Vec<Vec<f64>>,
+    /// Indices of `dates` within the evaluation range
+    range: std::ops::Range<usize>,
+}
+
+/// Metrics are NaN when the factor cannot be evaluated; call
+/// [`MarketEvaluator::evaluator`] to see the error.
+impl FactorEvaluator for MarketEvaluator {
+    fn ic(&self, factor: &dyn Factor, horizon: usize) -> f64 {
+        self.evaluator(factor)
+            .map_or(f64::NAN, |evaluator| evaluator.ic(horizon))
+    }
+
+    fn ir(&self, factor: &dyn Factor, horizon: usize) -> f64 {
+        self.evaluator(factor)
+            .map_or(f64::NAN, |evaluator| evaluator.ir(horizon))
+    }
+
+    fn turnover(&self, factor: &dyn Factor) -> f64 {
+        self.grid()
+            .and_then(|grid| self.compute_scores(&grid, factor))
+            .map_or(f64::NAN, |scores| {
+                signal_turnover(&scores, self.config.min_observations)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::performance::daily_dates;
+    use factors::{DataFrequency, FactorCategory};
+
+    /// Return since the first visible close, per symbol.
+    #[derive(Debug)]
+    struct Momentum;
+
+    impl Factor for Momentum {
+        fn name(&self) -> &str {
+            "momentum"
+        }
+
+        fn description(&self) -> &str {
+            "Return since the first date"
+        }
+
+        fn category(&self) -> FactorCategory {
+            FactorCategory::Momentum
+        }
+
+        fn required_columns(&self) -> &[&str] {
+            &["close"]
+        }
+
+        fn lookback(&self) -> usize {
+            1
+        }
+
+        fn frequency(&self) -> DataFrequency {
+            DataFrequency::Daily
+        }
+
+        fn compute_raw(&self, data: &LazyFrame, date: NaiveDate) -> factors::Result<DataFrame> {
+            data.clone()
+                .sort(["date"], Default::default())
+                .group_by([col("symbol")])
+                .agg([(col("close").last() / col("close").first() - lit(1.0)).alias("momentum")])
+                .with_column(lit(date).alias("date"))
+                .collect()
+        }
+    }
+
+    /// Four symbols compounding at different constant rates over 40 days.
+    fn market_data() -> MarketData {
+        let growth = [("A", 0.001), ("B", 0.002), ("C", 0.003), ("D", -0.001)];
+        let (mut symbols, mut dates, mut closes) = (Vec::new(), Vec::new(), Vec::new());
+        for (day, date) in daily_dates(40).into_iter().enumerate() {
+            for (symbol, rate) in growth {
+                symbols.push(symbol);
+                dates.push(date);
+                closes.push(100.0 * (1.0_f64 + rate).powi(day as i32));
+            }
+        }
+        MarketData::new(df! { "symbol" => symbols, "date" => dates, "close" => closes }.unwrap())
+    }
+
+    fn config() -> EvaluatorConfig {
+        EvaluatorConfig {
+            min_observations: 5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_factor_scores_use_only_past_data() {
+        let days = daily_dates(40);
+        let evaluator = MarketEvaluator::new(market_data(), days[10], days[20])
+            .with_universe(vec!["C".to_string(), "A".to_string()]);
+        let FactorScores {
+            dates,
+            symbols,
+            scores,
+        } = evaluator.factor_scores(&Momentum).unwrap();
+
+        assert_eq!(dates.len(), 11);
+        assert_eq!(symbols, vec!["C".to_string(), "A".to_string()]);
+        // Momentum on day 10 covers exactly ten periods of growth
+        assert!((scores[0][0] - (1.003_f64.powi(10) - 1.0)).abs() < 1e-12);
+        assert!((scores[0][1] - (1.001_f64.powi(10) - 1.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_factor_evaluator_end_to_end() {
+        let days = daily_dates(40);
+        let evaluator =
+            MarketEvaluator::new(market_data(), days[5], days[39]).with_config(config());
+
+        // Faster-growing names keep both higher momentum and higher returns
+        assert!((evaluator.ic(&Momentum, 1) - 1.0).abs() < 1e-12);
+        assert!((evaluator.ic(&Momentum, 5) - 1.0).abs() < 1e-12);
+        // Ranks never change, so there is no turnover
+        let turnover = evaluator.turnover(&Momentum);
+        assert!(turnover.is_nan() || turnover.abs() < 1e-12);
+
+        // One-period returns keep every horizon of the evaluator coherent
+        let fitted = evaluator.evaluator(&Momentum).unwrap();
+        assert_eq!(fitted.ic_time_series(5).len(), 35 - 5);
+        let decay = fitted.decay_analysis();
+        for (&horizon, &ic) in decay.curve.horizons.iter().zip(&decay.curve.ic_values) {
+            if horizon < 35 - 5 {
+                assert!((ic - 1.0).abs() < 1e-12, "horizon {horizon}: {ic}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_name_factors_are_not_confused() {
+        /// Momentum with its sign flipped, under the same name.
+        #[derive(Debug)]
+        struct Reversal;
+
+        impl Factor for Reversal {
+            fn name(&self) -> &str {
+                Momentum.name()
+            }
+            fn description(&self) -> &str {
+                Momentum.description()
+            }
+            fn category(&self) -> FactorCategory {
+                Momentum.category()
+            }
+            fn required_columns(&self) -> &[&str] {
+                Momentum.required_columns()
+            }
+            fn lookback(&self) -> usize {
+                Momentum.lookback()
+            }
+            fn frequency(&self) -> DataFrequency {
+                Momentum.frequency()
+            }
+            fn compute_raw(&self, data: &LazyFrame, date: NaiveDate) -> factors::Result<DataFrame> {
+                Momentum
+                    .compute_raw(data, date)?
+                    .lazy()
+                    .with_column(-col("momentum"))
+                    .collect()
+            }
+        }
+
+        let days = daily_dates(40);
+        let evaluator =
+            MarketEvaluator::new(market_data(), days[5], days[39]).with_config(config());
+        assert!((evaluator.ic(&Momentum, 1) - 1.0).abs() < 1e-12);
+        assert!((evaluator.ic(&Reversal, 1) + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let days = daily_dates(61);
+        let evaluator = MarketEvaluator::new(market_data(), days[50], days[60]);
+        assert!(matches!(
+            evaluator.factor_scores(&Momentum),
+            Err(TarifaError::InsufficientData(_))
+        ));
+        assert!(evaluator.ic(&Momentum, 1).is_nan());
+
+        let evaluator = MarketEvaluator::new(market_data(), days[0], days[39])
+            .with_universe(vec!["Z".to_string()]);
+        assert!(matches!(
+            evaluator.factor_scores(&Momentum),
+            Err(TarifaError::SymbolNotFound(_))
+        ));
+    }
+}
@@ -2,11 +2,43 @@
 //!
 //! This module provides various metrics for evaluating signal quality including:
 //! - Information Ratio (IR): mean IC / std IC
+//! - Newey-West (HAC) standard errors, t-stats and p-values for the mean IC
 //! - Signal Turnover: autocorrelation of signal ranks
+//! - Position Turnover: quantile membership turnover, holding periods and
+//!   implied trading costs
 //! - Aggregate metrics for comprehensive signal evaluation
 
+use ndarray::Array1;
 use serde::{Deserialize, Serialize};
 
+use crate::performance::normal_cdf;
+use crate::stats::finite_mean;
+
+/// Lag selection for Newey-West (HAC) standard errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HacLag {
+    /// Newey-West (1994) rule of thumb, `floor(4 * (n / 100)^(2/9))`
+    #[default]
+    Automatic,
+    /// `horizon - 1` lags, the overlap between consecutive forward returns
+    /// measured over `horizon` periods
+    Horizon(usize),
+    /// Fixed number of lags
+    Fixed(usize),
+}
+
+impl HacLag {
+    /// Number of lags for a series of `n_obs` observations, capped at `n_obs - 1`.
+    pub fn lags(&self, n_obs: usize) -> usize {
+        let lags = match *self {
+            Self::Automatic => (4.0 * (n_obs as f64 / 100.0).powf(2.0 / 9.0)).floor() as usize,
+            Self::Horizon(horizon) => horizon.saturating_sub(1),
+            Self::Fixed(lags) => lags,
+        };
+        lags.min(n_obs.saturating_sub(1))
+    }
+}
+
 /// Configuration for metrics calculation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricsConfig {
@@ -18,6 +50,9 @@ pub struct MetricsConfig {
     pub annualize: bool,
     /// Number of trading days per year for annualization
     pub trading_days_per_year: usize,
+    /// Lag selection for the HAC standard error of the mean IC
+    #[serde(default)]
+    pub hac_lag: HacLag,
 }
 
 impl Default for MetricsConfig {
@@ -27,6 +62,7 @@ impl Default for MetricsConfig {
             turnover_periods: 21,
             annualize: true,
             trading_days_per_year: 252,
+            hac_lag: HacLag::Automatic,
         }
     }
 }
@@ -35,6 +71,10 @@ impl Default for MetricsConfig {
 ///
 /// IR measures the consistency of a signal's predictive power.
 /// Higher IR indicates more reliable signals.
+///
+/// ICs computed daily over multi-day horizons overlap and are serially
+/// correlated, so the significance of the mean IC is tested with a Newey-West
+/// standard error rather than `std_ic / sqrt(n_obs)`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InformationRatio {
     /// Mean IC
@@ -43,6 +83,14 @@ pub struct InformationRatio {
     pub std_ic: f64,
     /// Information Ratio
     pub ir: f64,
+    /// Newey-West standard error of the mean IC
+    pub std_error: f64,
+    /// t-statistic of the mean IC using the Newey-West standard error
+    pub t_stat: f64,
+    /// Two-sided p-value of the t-statistic (normal approximation)
+    pub p_value: f64,
+    /// Number of lags used for the standard error
+    pub hac_lags: usize,
     /// Number of observations
     pub n_obs: usize,
 }
@@ -82,6 +130,10 @@ impl InformationRatio {
                 mean_ic: f64::NAN,
                 std_ic: f64::NAN,
                 ir: f64::NAN,
+                std_error: f64::NAN,
+                t_stat: f64::NAN,
+                p_value: f64::NAN,
+                hac_lags: 0,
                 n_obs,
             };
         }
@@ -106,10 +158,23 @@ impl InformationRatio {
             f64::NAN
         };
 
+        let hac_lags = config.hac_lag.lags(n_obs);
+        let std_error = newey_west_std_error(&valid_ics, hac_lags);
+        let t_stat = if std_error > 0.0 {
+            mean_ic / std_error
+        } else {
+            f64::NAN
+        };
+        let p_value = 2.0 * (1.0 - normal_cdf(t_stat.abs()));
+
         Self {
             mean_ic,
             std_ic,
             ir,
+            std_error,
+            t_stat,
+            p_value,
+            hac_lags,
             n_obs,
         }
     }
@@ -201,6 +266,188 @@ impl SignalTurnover {
     }
 }
 
+/// Configuration for position-level turnover analysis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnoverConfig {
+    /// Number of quantile portfolios
+    pub n_quantiles: usize,
+    /// Lags (in periods) of the rank autocorrelation
+    pub lags: Vec<usize>,
+    /// Rebalancing periods per year
+    pub periods_per_year: usize,
+}
+
+impl Default for TurnoverConfig {
+    fn default() -> Self {
+        Self {
+            n_quantiles: 5,
+            lags: vec![1, 5, 10, 21],
+            periods_per_year: 252,
+        }
+    }
+}
+
+/// Position-level turnover of quantile portfolios formed from signal ranks.
+///
+/// Quantiles are numbered from the lowest scores, so the last entry of
+/// `quantile_turnover` and `holding_period` is the long (top) quantile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionTurnover {
+    /// Lags of `rank_autocorr`
+    pub lags: Vec<usize>,
+    /// Mean cross-sectional rank correlation between periods `t - lag` and `t`
+    pub rank_autocorr: Vec<f64>,
+    /// Mean fraction of each quantile's members that were not in it the
+    /// previous period
+    pub quantile_turnover: Vec<f64>,
+    /// Membership turnover of the top quantile
+    pub top_turnover: f64,
+    /// Membership turnover of the bottom quantile
+    pub bottom_turnover: f64,
+    /// Mean number of consecutive periods an asset stays in each quantile
+    pub holding_period: Vec<f64>,
+    /// Annual traded notional of the equal-weighted top-minus-bottom
+    /// portfolio per unit of gross exposure
+    pub annual_turnover: f64,
+    /// Number of periods
+    pub n_obs: usize,
+}
+
+impl PositionTurnover {
+    /// Calculate position-level turnover from a rank (or score) time series.
+    ///
+    /// Periods with fewer finite values than quantiles have no quantile
+    /// members. Holding periods cut off by the start or end of the sample
+    /// count at their observed length, so they understate long holding
+    /// periods in short samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `rank_series` - Time series of signal ranks for each asset
+    /// * `config` - Quantiles, autocorrelation lags and rebalancing frequency
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::{PositionTurnover, TurnoverConfig};
+    ///
+    /// let turnover = PositionTurnover::calculate(&rank_series, &TurnoverConfig::default());
+    /// println!("Top quintile held {:.1} days", turnover.holding_period[4]);
+    /// println!("Cost at 10 bps: {:.2}% a year", turnover.annual_cost(10.0) * 100.0);
+    /// ```
+    pub fn calculate(rank_series: &[Vec<f64>], config: &TurnoverConfig) -> Self {
+        let n_quantiles = config.n_quantiles.max(1);
+        let n_obs = rank_series.len();
+
+        let rank_autocorr = config
+            .lags
+            .iter()
+            .map(|&lag| {
+                let correlations: Vec<f64> = (lag..n_obs)
+                    .map(|t| {
+                        crate::ic::calculate_ic(
+                            &Array1::from_vec(rank_series[t - lag].clone()),
+                            &Array1::from_vec(rank_series[t].clone()),
+                        )
+                    })
+                    .collect();
+                finite_mean(&correlations)
+            })
+            .collect();
+
+        let membership: Vec<Vec<Option<usize>>> = rank_series
+            .iter()
+            .map(|ranks| quantile_membership(ranks, n_quantiles))
+            .collect();
+
+        let quantile_turnover: Vec<f64> = (0..n_quantiles)
+            .map(|q| {
+                let rates: Vec<f64> = membership
+                    .windows(2)
+                    .filter(|pair| pair[0].iter().any(Option::is_some))
+                    .map(|pair| {
+                        let members: Vec<usize> = (0..pair[1].len())
+                            .filter(|&j| pair[1][j] == Some(q))
+                            .collect();
+                        let entered = members
+                            .iter()
+                            .filter(|&&j| pair[0].get(j).copied().flatten() != Some(q))
+                            .count();
+                        entered as f64 / members.len() as f64
+                    })
+                    .collect();
+                finite_mean(&rates)
+            })
+            .collect();
+
+        let holding_period = (0..n_quantiles)
+            .map(|q| {
+                let n_assets = membership.iter().map(Vec::len).max().unwrap_or(0);
+                let mut runs = Vec::new();
+                for j in 0..n_assets {
+                    let mut run = 0usize;
+                    for period in &membership {
+                        if period.get(j).copied().flatten() == Some(q) {
+                            run += 1;
+                        } else if run > 0 {
+                            runs.push(run as f64);
+                            run = 0;
+                        }
+                    }
+                    if run > 0 {
+                        runs.push(run as f64);
+                    }
+                }
+                finite_mean(&runs)
+            })
+            .collect();
+
+        let top_turnover = quantile_turnover[n_quantiles - 1];
+        let bottom_turnover = quantile_turnover[0];
+
+        // Replacing a fraction f of a leg sells f and buys f of that leg's
+        // capital; with both legs at unit weight this trades f_top + f_bottom
+        // per unit of gross exposure each period.
+        let annual_turnover = (top_turnover + bottom_turnover) * config.periods_per_year as f64;
+
+        Self {
+            lags: config.lags.clone(),
+            rank_autocorr,
+            quantile_turnover,
+            top_turnover,
+            bottom_turnover,
+            holding_period,
+            annual_turnover,
+            n_obs,
+        }
+    }
+
+    /// Annual trading cost as a fraction of gross exposure.
+    ///
+    /// # Arguments
+    ///
+    /// * `cost_bps` - Cost per unit of traded notional, in basis points
+    pub fn annual_cost(&self, cost_bps: f64) -> f64 {
+        self.annual_turnover * cost_bps / 10_000.0
+    }
+}
+
+/// Quantile of each asset in one period, `None` for missing values.
+fn quantile_membership(ranks: &[f64], n_quantiles: usize) -> Vec<Option<usize>> {
+    let mut order: Vec<usize> = (0..ranks.len()).filter(|&j| ranks[j].is_finite()).collect();
+    let n = order.len();
+    let mut membership = vec![None; ranks.len()];
+    if n < n_quantiles {
+        return membership;
+    }
+
+    order.sort_by(|&a, &b| ranks[a].total_cmp(&ranks[b]));
+    for (rank, j) in order.into_iter().enumerate() {
+        membership[j] = Some(rank * n_quantiles / n);
+    }
+    membership
+}
+
 /// Aggregate signal metrics.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignalMetrics {
@@ -267,6 +514,83 @@ impl SignalMetrics {
     }
 }
 
+/// Newey-West standard error of the mean of a series.
+///
+/// Autocovariances up to `lags` are weighted with the Bartlett kernel,
+/// `1 - k / (lags + 1)`, which keeps the long-run variance non-negative. With
+/// zero lags this is the usual standard error of the mean (with an `n`
+/// rather than `n - 1` denominator). Non-finite values are ignored.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use tarifa_eval::{HacLag, newey_west_std_error};
+///
+/// let lags = HacLag::Horizon(21).lags(ic_series.len());
+/// let se = newey_west_std_error(&ic_series, lags);
+/// ```
+pub fn newey_west_std_error(series: &[f64], lags: usize) -> f64 {
+    let values: Vec<f64> = series.iter().copied().filter(|x| x.is_finite()).collect();
+    let n = values.len();
+    if n < 2 {
+        return f64::NAN;
+    }
+
+    let mean = values.iter().sum::<f64>() / n as f64;
+    let deviations: Vec<f64> = values.iter().map(|x| x - mean).collect();
+    let autocovariance = |k: usize| {
+        deviations[k..]
+            .iter()
+            .zip(&deviations)
+            .map(|(a, b)| a * b)
+            .sum::<f64>()
+            / n as f64
+    };
+
+    let long_run_variance = (1..=lags.min(n - 1)).fold(autocovariance(0), |acc, k| {
+        acc + 2.0 * (1.0 - k as f64 / (lags + 1) as f64) * autocovariance(k)
+    });
+
+    (long_run_variance.max(0.0) / n as f64).sqrt()
+}
+
+/// t-statistic of the mean of a series.
+///
+/// With `hac_lags` of `None` the standard error is the classic `sd / sqrt(n)`
+/// (with an `n - 1` variance denominator); otherwise it is
+/// [`newey_west_std_error`] with that many lags. Non-finite values are
+/// ignored. Returns NaN with fewer than two observations or zero dispersion.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use tarifa_eval::{HacLag, t_stat};
+///
+/// let iid = t_stat(&ic_series, None);
+/// let hac = t_stat(&ic_series, Some(HacLag::Horizon(21).lags(ic_series.len())));
+/// ```
+pub fn t_stat(series: &[f64], hac_lags: Option<usize>) -> f64 {
+    let values: Vec<f64> = series.iter().copied().filter(|x| x.is_finite()).collect();
+    let n = values.len();
+    if n < 2 {
+        return f64::NAN;
+    }
+
+    let mean = values.iter().sum::<f64>() / n as f64;
+    let std_error = hac_lags.map_or_else(
+        || {
+            let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+            (variance / n as f64).sqrt()
+        },
+        |lags| newey_west_std_error(&values, lags),
+    );
+    if std_error > 0.0 {
+        mean / std_error
+    } else {
+        f64::NAN
+    }
+}
+
 /// Calculate autocorrelation at a given lag.
 fn calculate_autocorrelation(series: &[f64], lag: usize) -> f64 {
     if series.len() <= lag {
@@ -299,6 +623,7 @@ fn calculate_autocorrelation(series: &[f64], lag: usize) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rng::SplitMix64;
 
     #[test]
     fn test_information_ratio() {
@@ -329,6 +654,80 @@ mod tests {
         assert!(ir.mean_ic.is_finite() || ir.n_obs < config.min_observations);
     }
 
+    #[test]
+    fn test_information_ratio_significance() {
+        let ic_series: Vec<f64> = (0..100)
+            .map(|i| 0.03 + if i % 2 == 0 { 0.02 } else { -0.02 })
+            .collect();
+        let config = MetricsConfig {
+            min_observations: 2,
+            hac_lag: HacLag::Fixed(0),
+            ..Default::default()
+        };
+        let ir = InformationRatio::calculate(&ic_series, &config);
+
+        assert_eq!(ir.hac_lags, 0);
+        assert!((ir.t_stat - ir.mean_ic / ir.std_error).abs() < 1e-12);
+        assert!(ir.t_stat > 10.0);
+        assert!(ir.p_value < 1e-6);
+    }
+
+    #[test]
+    fn test_hac_lag_selection() {
+        assert_eq!(HacLag::Automatic.lags(100), 4);
+        assert_eq!(HacLag::Automatic.lags(1000), 6);
+        assert_eq!(HacLag::Horizon(21).lags(500), 20);
+        assert_eq!(HacLag::Horizon(1).lags(500), 0);
+        assert_eq!(HacLag::Fixed(10).lags(5), 4);
+    }
+
+    #[test]
+    fn test_newey_west_zero_lags() {
+        let series = vec![0.05, 0.03, 0.07, 0.02, 0.06, 0.04];
+        let n = series.len() as f64;
+        let mean = series.iter().sum::<f64>() / n;
+        let variance = series.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+
+        let se = newey_west_std_error(&series, 0);
+        assert!((se - (variance / n).sqrt()).abs() < 1e-15);
+        assert!(newey_west_std_error(&[0.01], 0).is_nan());
+    }
+
+    #[test]
+    fn test_newey_west_overlapping_series() {
+        // 21-period sums of white noise overlap like 21-day forward returns
+        let mut rng = SplitMix64::new(11);
+        let shocks: Vec<f64> = (0..400).map(|_| rng.next_normal()).collect();
+        let overlapping: Vec<f64> = shocks.windows(21).map(|w| w.iter().sum()).collect();
+
+        let naive = newey_west_std_error(&overlapping, 0);
+        let hac = newey_west_std_error(&overlapping, HacLag::Horizon(21).lags(overlapping.len()));
+        assert!(hac > naive);
+
+        let config = MetricsConfig {
+            min_observations: 2,
+            hac_lag: HacLag::Horizon(21),
+            ..Default::default()
+        };
+        let ir = InformationRatio::calculate(&overlapping, &config);
+        assert_eq!(ir.hac_lags, 20);
+        assert!((ir.std_error - hac).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_t_stat() {
+        let series = vec![0.05, 0.03, f64::NAN, 0.07, 0.02, 0.06, 0.04];
+        let finite: Vec<f64> = series.iter().copied().filter(|x| x.is_finite()).collect();
+        let n = finite.len() as f64;
+        let mean = finite.iter().sum::<f64>() / n;
+        let sd = (finite.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+
+        assert!((t_stat(&series, None) - mean / (sd / n.sqrt())).abs() < 1e-12);
+        assert!((t_stat(&series, Some(2)) - mean / newey_west_std_error(&series, 2)).abs() < 1e-12);
+        assert!(t_stat(&[0.01], None).is_nan());
+        assert!(t_stat(&[0.01, 0.01], None).is_nan());
+    }
+
     #[test]
     fn test_signal_turnover() {
         let rank_series = vec![
@@ -348,6 +747,82 @@ mod tests {
         assert_eq!(turnover.n_obs, 4);
     }
 
+    #[test]
+    fn test_position_turnover_stable_ranks() {
+        let rank_series: Vec<Vec<f64>> =
+            (0..10).map(|_| (0..10).map(f64::from).collect()).collect();
+        let config = TurnoverConfig {
+            n_quantiles: 5,
+            lags: vec![1, 3],
+            periods_per_year: 252,
+        };
+        let turnover = PositionTurnover::calculate(&rank_series, &config);
+
+        assert!(
+            turnover
+                .rank_autocorr
+                .iter()
+                .all(|&c| (c - 1.0).abs() < 1e-12)
+        );
+        assert_eq!(turnover.top_turnover, 0.0);
+        assert_eq!(turnover.bottom_turnover, 0.0);
+        assert!(turnover.holding_period.iter().all(|&h| h == 10.0));
+        assert_eq!(turnover.annual_cost(10.0), 0.0);
+    }
+
+    #[test]
+    fn test_position_turnover_rotation() {
+        // Two assets swap between the top and bottom halves every period
+        let rank_series: Vec<Vec<f64>> = (0..9)
+            .map(|t| {
+                if t % 2 == 0 {
+                    vec![0.0, 1.0, 2.0, 3.0]
+                } else {
+                    vec![2.0, 3.0, 0.0, 1.0]
+                }
+            })
+            .collect();
+        let config = TurnoverConfig {
+            n_quantiles: 2,
+            lags: vec![1, 2],
+            periods_per_year: 12,
+        };
+        let turnover = PositionTurnover::calculate(&rank_series, &config);
+
+        assert!((turnover.rank_autocorr[0] + 0.6).abs() < 1e-12);
+        assert!((turnover.rank_autocorr[1] - 1.0).abs() < 1e-12);
+        assert_eq!(turnover.top_turnover, 1.0);
+        assert_eq!(turnover.bottom_turnover, 1.0);
+        assert_eq!(turnover.holding_period, vec![1.0, 1.0]);
+        // Both legs fully replaced 12 times a year
+        assert_eq!(turnover.annual_turnover, 24.0);
+        assert!((turnover.annual_cost(10.0) - 0.024).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_position_turnover_missing_values() {
+        let rank_series = vec![
+            vec![0.0, 1.0, 2.0, 3.0],
+            vec![f64::NAN, 1.0, 2.0, 3.0],
+            vec![0.0, f64::NAN, f64::NAN, f64::NAN],
+            vec![0.0, 1.0, 2.0, 3.0],
+        ];
+        let config = TurnoverConfig {
+            n_quantiles: 2,
+            lags: vec![1],
+            periods_per_year: 252,
+        };
+        let turnover = PositionTurnover::calculate(&rank_series, &config);
+
+        assert_eq!(turnover.n_obs, 4);
+        assert!(turnover.rank_autocorr[0].is_finite());
+        // Asset 2 drops into the bottom half while asset 0 is missing; the
+        // empty period 2 is not counted as a rebalance into period 3
+        assert_eq!(turnover.top_turnover, 0.0);
+        assert!((turnover.bottom_turnover - 0.5).abs() < 1e-12);
+        assert!(turnover.holding_period.iter().all(|h| h.is_finite()));
+    }
+
     #[test]
     fn test_signal_metrics() {
         let ic_series = vec![0.05, 0.03, 0.07, 0.02, 0.06];
@@ -386,5 +861,15 @@ mod tests {
         assert_eq!(config.turnover_periods, 21);
         assert_eq!(config.trading_days_per_year, 252);
         assert!(config.annualize);
+        assert_eq!(config.hac_lag, HacLag::Automatic);
+    }
+
+    #[test]
+    fn test_metrics_config_without_hac_lag() {
+        let json = r#"{"min_observations":10,"turnover_periods":5,"annualize":false,
+            "trading_days_per_year":252}"#;
+        let config: MetricsConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.turnover_periods, 5);
+        assert_eq!(config.hac_lag, HacLag::Automatic);
     }
 }
@@ -0,0 +1,344 @@
+//! Monte Carlo null distributions for backtests.
+//!
+//! Re-runs a [`Backtest`] many times on the same universe, returns and costs
+//! with signals that carry no information, and locates the real signal's
+//! Sharpe ratio and mean IC within the resulting null distributions.
+//! Simulations run in parallel with rayon and are seeded per run, so results
+//! do not depend on the number of threads.
+
+use chrono::NaiveDate;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::backtest::Backtest;
+use crate::rng::SplitMix64;
+use crate::stats::finite_mean;
+
+/// How null signals are generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NullModel {
+    /// Shuffle each period's scores across the assets that have a score
+    #[default]
+    ShuffledScores,
+    /// Gaussian AR(1) scores per asset with the real signal's average
+    /// lag-1 autocorrelation, so null portfolios turn over like the real one
+    Autocorrelated,
+}
+
+/// Monte Carlo configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloConfig {
+    /// Number of null backtests
+    pub n_simulations: usize,
+    /// Null signal generator
+    pub model: NullModel,
+    /// Seed of the random number generator
+    pub seed: u64,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        Self {
+            n_simulations: 1000,
+            model: NullModel::ShuffledScores,
+            seed: 42,
+        }
+    }
+}
+
+/// Real backtest statistics against their null distributions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NullDistribution {
+    /// Sharpe ratio of the real signal
+    pub sharpe_ratio: f64,
+    /// Mean IC of the real signal
+    pub mean_ic: f64,
+    /// Sharpe ratios of the null backtests
+    pub null_sharpe_ratios: Vec<f64>,
+    /// Mean ICs of the null backtests
+    pub null_mean_ics: Vec<f64>,
+    /// Percentile (0-100) of the real Sharpe ratio in the null distribution
+    pub sharpe_percentile: f64,
+    /// Percentile (0-100) of the real mean IC in the null distribution
+    pub ic_percentile: f64,
+    /// One-sided p-value: share of null Sharpe ratios at least as high as the real one
+    pub sharpe_p_value: f64,
+    /// One-sided p-value: share of null mean ICs at least as high as the real one
+    pub ic_p_value: f64,
+}
+
+/// Monte Carlo driver for backtest null distributions.
+#[derive(Debug, Clone, Default)]
+pub struct MonteCarlo {
+    config: MonteCarloConfig,
+}
+
+impl MonteCarlo {
+    /// Create a Monte Carlo driver.
+    pub const fn new(config: MonteCarloConfig) -> Self {
+        Self { config }
+    }
+
+    /// Backtest the real signal and its null counterparts.
+    ///
+    /// Every null run uses the same backtest engine, returns and dates as the
+    /// real run; assets without a real score never receive a null score.
+    ///
+    /// # Arguments
+    ///
+    /// * `backtest` - Backtest engine (constructor, costs, schedule, ...)
+    /// * `signal_scores` - Real signal scores (periods x assets)
+    /// * `returns` - Asset returns (periods x assets)
+    /// * `dates` - Trading dates
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::{MonteCarlo, MonteCarloConfig, NullModel};
+    ///
+    /// let null = MonteCarlo::new(MonteCarloConfig {
+    ///     model: NullModel::Autocorrelated,
+    ///     ..Default::default()
+    /// })
+    /// .run(&backtest, &scores, &returns, &dates);
+    /// println!("Sharpe beats {:.1}% of random signals", null.sharpe_percentile);
+    /// ```
+    pub fn run(
+        &self,
+        backtest: &Backtest,
+        signal_scores: &[Vec<f64>],
+        returns: &[Vec<f64>],
+        dates: &[NaiveDate],
+    ) -> NullDistribution {
+        let actual = backtest.run(signal_scores, returns, dates);
+        let sharpe_ratio = actual.sharpe_ratio;
+        let mean_ic = finite_mean(&actual.ic_history);
+
+        let phi = match self.config.model {
+            NullModel::ShuffledScores => 0.0,
+            NullModel::Autocorrelated => mean_autocorrelation(signal_scores),
+        };
+
+        let (null_sharpe_ratios, null_mean_ics): (Vec<f64>, Vec<f64>) =
+            (0..self.config.n_simulations)
+                .into_par_iter()
+                .map(|i| {
+                    let mut rng = SplitMix64::new(self.config.seed.wrapping_add(i as u64));
+                    let scores = match self.config.model {
+                        NullModel::ShuffledScores => shuffled_scores(signal_scores, &mut rng),
+                        NullModel::Autocorrelated => ar1_scores(signal_scores, phi, &mut rng),
+                    };
+                    let result = backtest.run(&scores, returns, dates);
+                    (result.sharpe_ratio, finite_mean(&result.ic_history))
+                })
+                .unzip();
+
+        NullDistribution {
+            sharpe_percentile: percentile_of(&null_sharpe_ratios, sharpe_ratio),
+            ic_percentile: percentile_of(&null_mean_ics, mean_ic),
+            sharpe_p_value: upper_p_value(&null_sharpe_ratios, sharpe_ratio),
+            ic_p_value: upper_p_value(&null_mean_ics, mean_ic),
+            sharpe_ratio,
+            mean_ic,
+            null_sharpe_ratios,
+            null_mean_ics,
+        }
+    }
+}
+
+/// Permute each period's finite scores among the assets that have one.
+fn shuffled_scores(scores: &[Vec<f64>], rng: &mut SplitMix64) -> Vec<Vec<f64>> {
+    scores
+        .iter()
+        .map(|row| {
+            let slots: Vec<usize> = (0..row.len()).filter(|&j| row[j].is_finite()).collect();
+            let mut values: Vec<f64> = slots.iter().map(|&j| row[j]).collect();
+            for k in (1..values.len()).rev() {
+                values.swap(k, rng.next_index(k + 1));
+            }
+            let mut shuffled = row.clone();
+            for (&j, v) in slots.iter().zip(values) {
+                shuffled[j] = v;
+            }
+            shuffled
+        })
+        .collect()
+}
+
+/// Unit-variance AR(1) scores with coefficient `phi`, masked like `scores`.
+fn ar1_scores(scores: &[Vec<f64>], phi: f64, rng: &mut SplitMix64) -> Vec<Vec<f64>> {
+    let n_assets = scores.iter().map(Vec::len).max().unwrap_or(0);
+    let innovation = (1.0 - phi * phi).sqrt();
+    let mut state: Vec<f64> = (0..n_assets).map(|_| rng.next_normal()).collect();
+
+    scores
+        .iter()
+        .map(|row| {
+            for z in state.iter_mut() {
+                *z = phi * *z + innovation * rng.next_normal();
+            }
+            row.iter()
+                .zip(state.iter())
+                .map(|(&s, &z)| if s.is_finite() { z } else { f64::NAN })
+                .collect()
+        })
+        .collect()
+}
+
+/// Average lag-1 autocorrelation of each asset's score series.
+fn mean_autocorrelation(scores: &[Vec<f64>]) -> f64 {
+    let n_assets = scores.iter().map(Vec::len).max().unwrap_or(0);
+    let mut correlations = Vec::new();
+
+    for j in 0..n_assets {
+        let pairs: Vec<(f64, f64)> = scores
+            .windows(2)
+            .filter_map(|w| Some((*w[0].get(j)?, *w[1].get(j)?)))
+            .filter(|(a, b)| a.is_finite() && b.is_finite())
+            .collect();
+        if pairs.len() < 3 {
+            continue;
+        }
+        let n = pairs.len() as f64;
+        let (mx, my) = (
+            pairs.iter().map(|p| p.0).sum::<f64>() / n,
+            pairs.iter().map(|p| p.1).sum::<f64>() / n,
+        );
+        let cov: f64 = pairs.iter().map(|(a, b)| (a - mx) * (b - my)).sum();
+        let vx: f64 = pairs.iter().map(|(a, _)| (a - mx).powi(2)).sum();
+        let vy: f64 = pairs.iter().map(|(_, b)| (b - my).powi(2)).sum();
+        if vx > 0.0 && vy > 0.0 {
+            correlations.push(cov / (vx * vy).sqrt());
+        }
+    }
+
+    let phi = finite_mean(&correlations);
+    if phi.is_finite() {
+        phi.clamp(-0.999, 0.999)
+    } else {
+        0.0
+    }
+}
+
+/// Percentile (0-100) of `value` among the finite `samples`, counting ties as half.
+fn percentile_of(samples: &[f64], value: f64) -> f64 {
+    let finite: Vec<f64> = samples.iter().copied().filter(|x| x.is_finite()).collect();
+    if finite.is_empty() || !value.is_finite() {
+        return f64::NAN;
+    }
+    let below = finite.iter().filter(|&&x| x < value).count() as f64;
+    let ties = finite.iter().filter(|&&x| x == value).count() as f64;
+    100.0 * (below + 0.5 * ties) / finite.len() as f64
+}
+
+/// Share of finite `samples` at least as large as `value`, with the usual +1 correction.
+fn upper_p_value(samples: &[f64], value: f64) -> f64 {
+    let finite: Vec<f64> = samples.iter().copied().filter(|x| x.is_finite()).collect();
+    if finite.is_empty() || !value.is_finite() {
+        return f64::NAN;
+    }
+    let extreme = finite.iter().filter(|&&x| x >= value).count();
+    (extreme + 1) as f64 / (finite.len() + 1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::BacktestConfig;
+    use crate::performance::daily_dates;
+    use approx::assert_relative_eq;
+
+    fn sample_data(n_periods: usize) -> (Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<NaiveDate>) {
+        let dates = daily_dates(n_periods);
+        let mut rng = SplitMix64::new(5);
+        let scores: Vec<Vec<f64>> = (0..n_periods)
+            .map(|_| (0..10).map(|_| rng.next_normal()).collect())
+            .collect();
+        // Next-period returns follow today's scores
+        let returns: Vec<Vec<f64>> = (0..n_periods)
+            .map(|t| {
+                let prev = &scores[t.saturating_sub(1)];
+                prev.iter()
+                    .map(|s| 0.002 * s + 0.005 * rng.next_normal())
+                    .collect()
+            })
+            .collect();
+        (scores, returns, dates)
+    }
+
+    fn daily_backtest() -> Backtest {
+        Backtest::new(BacktestConfig {
+            rebalance_frequency: 1,
+            transaction_cost_bps: 0.0,
+            n_long: Some(3),
+            n_short: Some(3),
+            max_position_size: 1.0,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_informative_signal_beats_shuffles() {
+        let (scores, returns, dates) = sample_data(120);
+        // Trade on yesterday's scores so positions line up with `returns`
+        let lagged: Vec<Vec<f64>> = (0..scores.len())
+            .map(|t| scores[t.saturating_sub(1)].clone())
+            .collect();
+        let config = MonteCarloConfig {
+            n_simulations: 50,
+            ..Default::default()
+        };
+        let null = MonteCarlo::new(config).run(&daily_backtest(), &lagged, &returns, &dates);
+
+        assert_eq!(null.null_sharpe_ratios.len(), 50);
+        assert!(null.sharpe_percentile > 95.0);
+        assert!(null.sharpe_p_value < 0.05);
+        assert!(null.ic_percentile.is_finite());
+    }
+
+    #[test]
+    fn test_seeded_reproducible() {
+        let (scores, returns, dates) = sample_data(40);
+        let config = MonteCarloConfig {
+            n_simulations: 8,
+            model: NullModel::Autocorrelated,
+            seed: 3,
+        };
+        let a = MonteCarlo::new(config.clone()).run(&daily_backtest(), &scores, &returns, &dates);
+        let b = MonteCarlo::new(config).run(&daily_backtest(), &scores, &returns, &dates);
+
+        assert_eq!(a.null_sharpe_ratios, b.null_sharpe_ratios);
+        assert_eq!(a.null_mean_ics, b.null_mean_ics);
+    }
+
+    #[test]
+    fn test_shuffle_preserves_values_and_mask() {
+        let scores = vec![vec![1.0, f64::NAN, 3.0, 4.0]];
+        let shuffled = shuffled_scores(&scores, &mut SplitMix64::new(1));
+
+        assert!(shuffled[0][1].is_nan());
+        let mut values: Vec<f64> = shuffled[0]
+            .iter()
+            .copied()
+            .filter(|x| x.is_finite())
+            .collect();
+        values.sort_by(f64::total_cmp);
+        assert_eq!(values, vec![1.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_ar1_matches_autocorrelation() {
+        let scores = vec![vec![0.0; 20]; 500];
+        let simulated = ar1_scores(&scores, 0.8, &mut SplitMix64::new(9));
+        assert_relative_eq!(mean_autocorrelation(&simulated), 0.8, epsilon = 0.05);
+    }
+
+    #[test]
+    fn test_percentile_and_p_value() {
+        let samples = vec![1.0, 2.0, 3.0, 4.0];
+        assert_relative_eq!(percentile_of(&samples, 3.0), 62.5);
+        assert_relative_eq!(percentile_of(&samples, 10.0), 100.0);
+        assert_relative_eq!(upper_p_value(&samples, 3.0), 3.0 / 5.0);
+        assert!(percentile_of(&[], 1.0).is_nan());
+    }
+}
@@ -0,0 +1,553 @@
+//! Symbol-keyed panel alignment.
+//!
+//! Converts long-format data (one row per symbol and date) into the dense
+//! dates x assets matrices consumed by the backtester. Every column of the
+//! resulting panel is pinned to a single symbol, so assets that enter or leave
+//! the universe show up as missing values rather than shifting other assets
+//! into the wrong column.
+
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::NaiveDate;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use tarifa_traits::{MarketData, Result, Symbol, TarifaError};
+
+use crate::costs::{CostInputs, DEFAULT_ESTIMATION_WINDOW};
+
+/// Default name of the symbol column.
+pub const SYMBOL_COLUMN: &str = "symbol";
+/// Default name of the date column.
+pub const DATE_COLUMN: &str = "date";
+/// Default name of the signal score column.
+pub const SCORE_COLUMN: &str = "score";
+/// Default name of the per-period return column.
+pub const RETURN_COLUMN: &str = "return";
+/// Name of the close price column used to derive returns.
+pub const CLOSE_COLUMN: &str = "close";
+/// Name of the daily high price column.
+pub const HIGH_COLUMN: &str = "high";
+/// Name of the daily low price column.
+pub const LOW_COLUMN: &str = "low";
+/// Name of the bid price column.
+pub const BID_COLUMN: &str = "bid";
+/// Name of the ask price column.
+pub const ASK_COLUMN: &str = "ask";
+/// Name of the share volume column.
+pub const VOLUME_COLUMN: &str = "volume";
+
+/// Coverage statistics for a single period of a panel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodCoverage {
+    /// Period date
+    pub date: NaiveDate,
+    /// Number of assets with a finite score
+    pub n_scored: usize,
+    /// Number of scored assets that also have a finite forward return
+    pub n_aligned: usize,
+    /// Number of assets scored this period but not in the previous one
+    pub n_entered: usize,
+    /// Number of assets scored in the previous period but not in this one
+    pub n_exited: usize,
+    /// Fraction of scored assets with a forward return (`n_aligned / n_scored`)
+    pub coverage: f64,
+}
+
+/// Signal scores and forward returns aligned by symbol and date.
+///
+/// `scores[t][j]` and `forward_returns[t][j]` always refer to `symbols[j]` on
+/// `dates[t]`. Missing observations are stored as `NaN`.
+#[derive(Debug, Clone)]
+pub struct Panel {
+    /// Sorted, unique period dates
+    pub dates: Vec<NaiveDate>,
+    /// Sorted, unique symbols (one per column)
+    pub symbols: Vec<Symbol>,
+    /// Signal scores (dates x symbols)
+    pub scores: Vec<Vec<f64>>,
+    /// Forward returns from each date to the next panel date (dates x symbols)
+    pub forward_returns: Vec<Vec<f64>>,
+    /// Prices, spreads, volume and volatility used to price trades
+    pub cost_inputs: CostInputs,
+}
+
+impl Panel {
+    /// Build a panel from a long-format DataFrame.
+    ///
+    /// The frame must contain `symbol`, `date`, `score` and `return` columns.
+    /// `return` is the return realized over the period *ending* on `date`
+    /// (e.g. close-to-close from the previous date). Scores are paired with the
+    /// same symbol's return on the next panel date, so a score observed on `t`
+    /// is only ever matched with a return realized after `t`.
+    ///
+    /// Optional `close`, `high`, `low`, `bid`, `ask` and `volume` columns are
+    /// picked up as [`CostInputs`] for the transaction cost models.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required column is missing, has an unexpected
+    /// type, or if a (symbol, date) pair appears more than once.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::Panel;
+    ///
+    /// let panel = Panel::from_frame(&df)?;
+    /// let result = backtest.run_panel(&panel);
+    /// ```
+    pub fn from_frame(df: &DataFrame) -> Result<Self> {
+        Self::from_frame_with_columns(df, SCORE_COLUMN, RETURN_COLUMN)
+    }
+
+    /// Build a panel from a long-format DataFrame with custom column names.
+    ///
+    /// # Arguments
+    ///
+    /// * `df` - Long-format frame with `symbol` and `date` columns
+    /// * `score_col` - Name of the signal score column
+    /// * `return_col` - Name of the per-period realized return column
+    pub fn from_frame_with_columns(
+        df: &DataFrame,
+        score_col: &str,
+        return_col: &str,
+    ) -> Result<Self> {
+        let keys = PanelKeys::from_frame(df)?;
+        let scores = keys.pivot(&float_column(df, score_col)?)?;
+        let realized = keys.pivot(&float_column(df, return_col)?)?;
+        let cost_inputs = keys.cost_inputs(df)?;
+
+        Ok(keys.into_panel(scores, &realized, cost_inputs))
+    }
+
+    /// Build a panel from market data.
+    ///
+    /// Uses the `return` column when present; otherwise per-period returns are
+    /// derived from consecutive `close` prices of each symbol.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `score` column is missing, or if neither a
+    /// `return` nor a `close` column is available.
+    pub fn from_market_data(data: &MarketData) -> Result<Self> {
+        if data.has_column(RETURN_COLUMN) {
+            return Self::from_frame(data.data());
+        }
+
+        let df = data.data();
+        let keys = PanelKeys::from_frame(df)?;
+        let scores = keys.pivot(&float_column(df, SCORE_COLUMN)?)?;
+        let closes = keys.pivot(&float_column(df, CLOSE_COLUMN)?)?;
+
+        let mut realized = vec![vec![f64::NAN; keys.symbols.len()]; keys.dates.len()];
+        for t in 1..closes.len() {
+            for j in 0..keys.symbols.len() {
+                let prev = closes[t - 1][j];
+                let cur = closes[t][j];
+                if prev.is_finite() && cur.is_finite() && prev > 0.0 {
+                    realized[t][j] = cur / prev - 1.0;
+                }
+            }
+        }
+
+        let cost_inputs = keys.cost_inputs(df)?;
+
+        Ok(keys.into_panel(scores, &realized, cost_inputs))
+    }
+
+    /// Number of periods in the panel.
+    pub const fn n_periods(&self) -> usize {
+        self.dates.len()
+    }
+
+    /// Number of distinct symbols in the panel.
+    pub const fn n_assets(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Per-period coverage of scores and forward returns.
+    pub fn coverage(&self) -> Vec<PeriodCoverage> {
+        period_coverage(&self.scores, &self.forward_returns, &self.dates)
+    }
+}
+
+/// Compute per-period coverage of a dates x assets score and return panel.
+///
+/// Columns are treated as fixed asset identities. On the first period every
+/// scored asset counts as entering.
+pub fn period_coverage(
+    scores: &[Vec<f64>],
+    returns: &[Vec<f64>],
+    dates: &[NaiveDate],
+) -> Vec<PeriodCoverage> {
+    let n_periods = scores.len().min(returns.len()).min(dates.len());
+    let mut coverage = Vec::with_capacity(n_periods);
+    let mut prev_scored: Vec<bool> = Vec::new();
+
+    for t in 0..n_periods {
+        let scored: Vec<bool> = scores[t].iter().map(|s| s.is_finite()).collect();
+        let n_scored = scored.iter().filter(|&&s| s).count();
+        let n_aligned = scored
+            .iter()
+            .zip(returns[t].iter())
+            .filter(|&(&s, r)| s && r.is_finite())
+            .count();
+
+        let was_scored = |j: usize| prev_scored.get(j).copied().unwrap_or(false);
+        let n_entered = (0..scored.len())
+            .filter(|&j| scored[j] && !was_scored(j))
+            .count();
+        let n_exited = (0..prev_scored.len())
+            .filter(|&j| prev_scored[j] && !scored.get(j).copied().unwrap_or(false))
+            .count();
+
+        coverage.push(PeriodCoverage {
+            date: dates[t],
+            n_scored,
+            n_aligned,
+            n_entered,
+            n_exited,
+            coverage: if n_scored > 0 {
+                n_aligned as f64 / n_scored as f64
+            } else {
+                f64::NAN
+            },
+        });
+
+        prev_scored = scored;
+    }
+
+    coverage
+}
+
+/// Row positions of a long-format frame within the dates x symbols grid.
+#[derive(Debug)]
+pub(crate) struct PanelKeys {
+    pub(crate) dates: Vec<NaiveDate>,
+    pub(crate) symbols: Vec<Symbol>,
+    /// (date index, symbol index) for each row of the source frame
+    rows: Vec<Option<(usize, usize)>>,
+}
+
+impl PanelKeys {
+    pub(crate) fn from_frame(df: &DataFrame) -> Result<Self> {
+        let symbol_col = df
+            .column(SYMBOL_COLUMN)
+            .map_err(|_| TarifaError::MissingColumn(SYMBOL_COLUMN.to_string()))?;
+        let row_symbols: Vec<Option<String>> = symbol_col
+            .as_materialized_series()
+            .str()?
+            .into_iter()
+            .map(|s| s.map(str::to_string))
+            .collect();
+
+        let date_col = df
+            .column(DATE_COLUMN)
+            .map_err(|_| TarifaError::MissingColumn(DATE_COLUMN.to_string()))?
+            .cast(&DataType::Date)?;
+        let row_dates: Vec<Option<NaiveDate>> = date_col
+            .as_materialized_series()
+            .date()?
+            .as_date_iter()
+            .collect();
+
+        let dates: Vec<NaiveDate> = row_dates
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let symbols: Vec<Symbol> = row_symbols
+            .iter()
+            .flatten()
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        let date_index: HashMap<NaiveDate, usize> =
+            dates.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+        let symbol_index: HashMap<&str, usize> = symbols
+            .iter()
+            .enumerate()
+            .map(|(j, s)| (s.as_str(), j))
+            .collect();
+
+        let mut seen = vec![vec![false; symbols.len()]; dates.len()];
+        let mut rows = Vec::with_capacity(df.height());
+        for (symbol, date) in row_symbols.iter().zip(row_dates.iter()) {
+            let key = match (symbol, date) {
+                (Some(s), Some(d)) => Some((date_index[d], symbol_index[s.as_str()])),
+                _ => None,
+            };
+            if let Some((t, j)) = key {
+                if seen[t][j] {
+                    return Err(TarifaError::InvalidData(format!(
+                        "Duplicate row for symbol '{}' on {}",
+                        symbols[j], dates[t]
+                    )));
+                }
+                seen[t][j] = true;
+            }
+            rows.push(key);
+        }
+
+        Ok(Self {
+            dates,
+            symbols,
+            rows,
+        })
+    }
+
+    /// Scatter a per-row column into a dates x symbols matrix.
+    pub(crate) fn pivot(&self, values: &[Option<f64>]) -> Result<Vec<Vec<f64>>> {
+        if values.len() != self.rows.len() {
+            return Err(TarifaError::InvalidData(format!(
+                "Column has {} rows, expected {}",
+                values.len(),
+                self.rows.len()
+            )));
+        }
+
+        let mut matrix = vec![vec![f64::NAN; self.symbols.len()]; self.dates.len()];
+        for (key, value) in self.rows.iter().zip(values.iter()) {
+            if let (Some((t, j)), Some(v)) = (key, value) {
+                matrix[*t][*j] = *v;
+            }
+        }
+
+        Ok(matrix)
+    }
+
+    /// Pivot an optional column, returning `None` if the frame lacks it.
+    fn pivot_optional(&self, df: &DataFrame, name: &str) -> Result<Option<Vec<Vec<f64>>>> {
+        if df.column(name).is_err() {
+            return Ok(None);
+        }
+        Ok(Some(self.pivot(&float_column(df, name)?)?))
+    }
+
+    /// Collect whichever cost model inputs the frame provides.
+    fn cost_inputs(&self, df: &DataFrame) -> Result<CostInputs> {
+        let mut inputs = self
+            .pivot_optional(df, CLOSE_COLUMN)?
+            .map_or_else(CostInputs::default, |prices| {
+                CostInputs::from_prices(prices, DEFAULT_ESTIMATION_WINDOW)
+            });
+
+        if let Some(volume) = self.pivot_optional(df, VOLUME_COLUMN)? {
+            inputs = inputs.with_volume(&volume, DEFAULT_ESTIMATION_WINDOW);
+        }
+        if let (Some(high), Some(low)) = (
+            self.pivot_optional(df, HIGH_COLUMN)?,
+            self.pivot_optional(df, LOW_COLUMN)?,
+        ) {
+            inputs = inputs.with_high_low(&high, &low);
+        }
+        if let (Some(bid), Some(ask)) = (
+            self.pivot_optional(df, BID_COLUMN)?,
+            self.pivot_optional(df, ASK_COLUMN)?,
+        ) {
+            inputs = inputs.with_quotes(&bid, &ask);
+        }
+
+        Ok(inputs)
+    }
+
+    /// Shift realized returns back one period to pair them with scores.
+    fn into_panel(
+        self,
+        scores: Vec<Vec<f64>>,
+        realized: &[Vec<f64>],
+        cost_inputs: CostInputs,
+    ) -> Panel {
+        let n_assets = self.symbols.len();
+        let forward_returns = (0..self.dates.len())
+            .map(|t| {
+                realized
+                    .get(t + 1)
+                    .cloned()
+                    .unwrap_or_else(|| vec![f64::NAN; n_assets])
+            })
+            .collect();
+
+        Panel {
+            dates: self.dates,
+            symbols: self.symbols,
+            scores,
+            forward_returns,
+            cost_inputs,
+        }
+    }
+}
+
+/// Read a numeric column as `f64` values.
+pub(crate) fn float_column(df: &DataFrame, name: &str) -> Result<Vec<Option<f64>>> {
+    let column = df
+        .column(name)
+        .map_err(|_| TarifaError::MissingColumn(name.to_string()))?
+        .cast(&DataType::Float64)?;
+
+    Ok(column.as_materialized_series().f64()?.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    fn long_frame() -> DataFrame {
+        // AAA is present throughout, BBB leaves after day 2, CCC enters on day 2
+        df! {
+            "symbol" => &["AAA", "BBB", "AAA", "BBB", "CCC", "AAA", "CCC"],
+            "date" => &[date(1), date(1), date(2), date(2), date(2), date(3), date(3)],
+            "score" => &[1.0, -1.0, 0.5, -0.5, 0.0, 0.2, 0.3],
+            "return" => &[0.00, 0.00, 0.01, -0.02, 0.00, 0.03, 0.04],
+        }
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_frame_shape() {
+        let panel = Panel::from_frame(&long_frame()).unwrap();
+
+        assert_eq!(panel.dates, vec![date(1), date(2), date(3)]);
+        assert_eq!(panel.symbols, vec!["AAA", "BBB", "CCC"]);
+        assert_eq!(panel.n_periods(), 3);
+        assert_eq!(panel.n_assets(), 3);
+    }
+
+    #[test]
+    fn test_from_frame_aligns_forward_returns() {
+        let panel = Panel::from_frame(&long_frame()).unwrap();
+
+        // Day 1 scores pair with day 2 returns
+        assert!((panel.forward_returns[0][0] - 0.01).abs() < 1e-12);
+        assert!((panel.forward_returns[0][1] + 0.02).abs() < 1e-12);
+        // CCC had no score on day 1
+        assert!(panel.scores[0][2].is_nan());
+
+        // Day 2 scores pair with day 3 returns; BBB has left the universe
+        assert!((panel.forward_returns[1][0] - 0.03).abs() < 1e-12);
+        assert!(panel.forward_returns[1][1].is_nan());
+        assert!((panel.forward_returns[1][2] - 0.04).abs() < 1e-12);
+
+        // No forward return after the last date
+        assert!(panel.forward_returns[2].iter().all(|r| r.is_nan()));
+    }
+
+    #[test]
+    fn test_from_frame_row_order_independent() {
+        let df = long_frame();
+        let reversed = df.reverse();
+
+        let a = Panel::from_frame(&df).unwrap();
+        let b = Panel::from_frame(&reversed).unwrap();
+
+        assert_eq!(a.symbols, b.symbols);
+        for t in 0..a.n_periods() {
+            for j in 0..a.n_assets() {
+                let (x, y) = (a.scores[t][j], b.scores[t][j]);
+                assert!(x == y || (x.is_nan() && y.is_nan()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_frame_missing_column() {
+        let df = df! {
+            "symbol" => &["AAA"],
+            "date" => &[date(1)],
+            "score" => &[1.0],
+        }
+        .unwrap();
+
+        let result = Panel::from_frame(&df);
+        assert!(matches!(result, Err(TarifaError::MissingColumn(c)) if c == "return"));
+    }
+
+    #[test]
+    fn test_from_frame_duplicate_rows() {
+        let df = df! {
+            "symbol" => &["AAA", "AAA"],
+            "date" => &[date(1), date(1)],
+            "score" => &[1.0, 2.0],
+            "return" => &[0.0, 0.0],
+        }
+        .unwrap();
+
+        let result = Panel::from_frame(&df);
+        assert!(matches!(result, Err(TarifaError::InvalidData(_))));
+    }
+
+    #[test]
+    fn test_from_market_data_uses_close() {
+        let df = df! {
+            "symbol" => &["AAA", "BBB", "AAA", "BBB", "AAA", "BBB"],
+            "date" => &[date(1), date(1), date(2), date(2), date(3), date(3)],
+            "close" => &[100.0, 50.0, 110.0, 45.0, 99.0, 45.0],
+            "score" => &[1.0, -1.0, 1.0, -1.0, 1.0, -1.0],
+        }
+        .unwrap();
+
+        let panel = Panel::from_market_data(&MarketData::new(df)).unwrap();
+
+        assert!((panel.forward_returns[0][0] - 0.10).abs() < 1e-12);
+        assert!((panel.forward_returns[0][1] + 0.10).abs() < 1e-12);
+        assert!((panel.forward_returns[1][0] + 0.10).abs() < 1e-12);
+        assert!(panel.forward_returns[1][1].abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_from_frame_cost_inputs() {
+        let df = df! {
+            "symbol" => &["AAA", "AAA"],
+            "date" => &[date(1), date(2)],
+            "score" => &[1.0, 1.0],
+            "return" => &[0.0, 0.01],
+            "close" => &[100.0, 101.0],
+            "volume" => &[1000.0, 3000.0],
+            "bid" => &[99.9, 100.9],
+            "ask" => &[100.1, 101.1],
+        }
+        .unwrap();
+
+        let panel = Panel::from_frame(&df).unwrap();
+        let context = panel.cost_inputs.context(1, 1.0);
+
+        assert_eq!(context.price(0), Some(101.0));
+        assert_eq!(context.adv(0), Some(2000.0));
+        assert!((context.spread(0).unwrap() - 0.2 / 101.0).abs() < 1e-12);
+
+        // Frames without market columns have no cost inputs
+        let panel = Panel::from_frame(&long_frame()).unwrap();
+        assert!(panel.cost_inputs.prices.is_empty());
+    }
+
+    #[test]
+    fn test_coverage() {
+        let panel = Panel::from_frame(&long_frame()).unwrap();
+        let coverage = panel.coverage();
+
+        assert_eq!(coverage.len(), 3);
+
+        assert_eq!(coverage[0].n_scored, 2);
+        assert_eq!(coverage[0].n_aligned, 2);
+        assert_eq!(coverage[0].n_entered, 2);
+        assert!((coverage[0].coverage - 1.0).abs() < 1e-12);
+
+        // BBB is scored on day 2 but has no day-3 return
+        assert_eq!(coverage[1].n_scored, 3);
+        assert_eq!(coverage[1].n_aligned, 2);
+        assert_eq!(coverage[1].n_entered, 1);
+        assert_eq!(coverage[1].n_exited, 0);
+
+        assert_eq!(coverage[2].n_scored, 2);
+        assert_eq!(coverage[2].n_aligned, 0);
+        assert_eq!(coverage[2].n_exited, 1);
+    }
+}
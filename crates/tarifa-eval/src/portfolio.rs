@@ -0,0 +1,636 @@
+//! Portfolio construction strategies.
+//!
+//! A [`PortfolioConstructor`] turns one cross-section of signal scores into
+//! target portfolio weights. The backtester calls it on every rebalance date
+//! and then applies the configured position limits to the result.
+//!
+//! Built-in strategies:
+//! - [`TopBottomConstructor`]: equal-weight top-N / bottom-N baskets
+//! - [`ScoreProportionalConstructor`]: weights proportional to raw scores
+//! - [`RankLinearConstructor`]: weights linear in cross-sectional rank
+//! - [`QuantileConstructor`]: equal-weight top and bottom quantiles
+//! - [`InverseVolatilityConstructor`]: top-N / bottom-N weighted by inverse volatility
+//! - [`ZScoreConstructor`]: dollar-neutral weights proportional to z-scores
+
+use serde::{Deserialize, Serialize};
+
+use crate::backtest::BacktestConfig;
+use crate::stats::{mean, std_dev};
+
+/// Market information available to a constructor on a rebalance date.
+#[derive(Debug, Clone, Copy)]
+pub struct PortfolioContext<'a> {
+    /// Asset returns realized before the rebalance date (periods x assets),
+    /// oldest first. Columns line up with the score vector.
+    pub returns_history: &'a [Vec<f64>],
+}
+
+impl<'a> PortfolioContext<'a> {
+    /// Create a context from the trailing return history.
+    pub const fn new(returns_history: &'a [Vec<f64>]) -> Self {
+        Self { returns_history }
+    }
+}
+
+/// Converts signal scores into target portfolio weights.
+///
+/// Weights are fractions of capital: positive for long positions, negative for
+/// short positions and zero for names that are not held. Assets with non-finite
+/// scores must receive a zero weight. All implementations must be thread-safe
+/// (Send + Sync).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tarifa_eval::{PortfolioConstructor, PortfolioContext};
+///
+/// struct LongTheBest;
+///
+/// impl PortfolioConstructor for LongTheBest {
+///     fn construct(&self, scores: &[f64], _context: &PortfolioContext<'_>) -> Vec<f64> {
+///         let mut weights = vec![0.0; scores.len()];
+///         let best = scores
+///             .iter()
+///             .enumerate()
+///             .filter(|(_, s)| s.is_finite())
+///             .max_by(|a, b| a.1.total_cmp(b.1))
+///             .map(|(i, _)| i);
+///         if let Some(i) = best {
+///             weights[i] = 1.0;
+///         }
+///         weights
+///     }
+///
+///     fn name(&self) -> &str {
+///         "long_the_best"
+///     }
+/// }
+/// ```
+pub trait PortfolioConstructor: Send + Sync {
+    /// Compute target weights for one cross-section of scores.
+    ///
+    /// # Arguments
+    ///
+    /// * `scores` - Signal scores for each asset (may contain NaN)
+    /// * `context` - Market information available on the rebalance date
+    ///
+    /// # Returns
+    ///
+    /// A weight vector with the same length as `scores`.
+    fn construct(&self, scores: &[f64], context: &PortfolioContext<'_>) -> Vec<f64>;
+
+    /// Name of this construction strategy.
+    fn name(&self) -> &str;
+}
+
+/// Equal-weight top-N long / bottom-N short baskets.
+///
+/// Each long name receives `1 / n_long` and each short name `-1 / n_short`.
+/// This is the backtester's default construction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopBottomConstructor {
+    /// Number of long positions (defaults to half the universe, or all of it when long-only)
+    pub n_long: Option<usize>,
+    /// Number of short positions (defaults to half the universe)
+    pub n_short: Option<usize>,
+    /// Whether to hold a short book
+    pub long_short: bool,
+}
+
+impl TopBottomConstructor {
+    /// Create a top/bottom constructor.
+    pub const fn new(n_long: Option<usize>, n_short: Option<usize>, long_short: bool) -> Self {
+        Self {
+            n_long,
+            n_short,
+            long_short,
+        }
+    }
+
+    /// Create a constructor matching the basket settings of a backtest config.
+    pub const fn from_config(config: &BacktestConfig) -> Self {
+        Self::new(config.n_long, config.n_short, config.long_short)
+    }
+}
+
+impl Default for TopBottomConstructor {
+    fn default() -> Self {
+        Self::from_config(&BacktestConfig::default())
+    }
+}
+
+impl PortfolioConstructor for TopBottomConstructor {
+    fn construct(&self, scores: &[f64], _context: &PortfolioContext<'_>) -> Vec<f64> {
+        let n_assets = scores.len();
+        let mut positions = vec![0.0; n_assets];
+        let ranked = ranked_desc(scores);
+
+        if ranked.is_empty() {
+            return positions;
+        }
+
+        if self.long_short {
+            let n_long = self.n_long.unwrap_or(n_assets / 2);
+            let n_short = self.n_short.unwrap_or(n_assets / 2);
+
+            let long_weight = if n_long > 0 { 1.0 / n_long as f64 } else { 0.0 };
+            for &idx in ranked.iter().take(n_long) {
+                positions[idx] = long_weight;
+            }
+
+            let short_weight = if n_short > 0 {
+                -1.0 / n_short as f64
+            } else {
+                0.0
+            };
+            for &idx in ranked.iter().skip(ranked.len().saturating_sub(n_short)) {
+                positions[idx] = short_weight;
+            }
+        } else {
+            let n_long = self.n_long.unwrap_or(n_assets);
+            let weight = if n_long > 0 { 1.0 / n_long as f64 } else { 0.0 };
+
+            for &idx in ranked.iter().take(n_long) {
+                positions[idx] = weight;
+            }
+        }
+
+        positions
+    }
+
+    fn name(&self) -> &str {
+        "top_bottom"
+    }
+}
+
+/// Weights proportional to raw signal scores.
+///
+/// Long-short: positive scores form the long book (summing to 1) and negative
+/// scores the short book (summing to -1). Long-only: only positive scores are
+/// held, scaled to sum to 1.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScoreProportionalConstructor {
+    /// Whether to hold a short book
+    pub long_short: bool,
+}
+
+impl ScoreProportionalConstructor {
+    /// Create a score-proportional constructor.
+    pub const fn new(long_short: bool) -> Self {
+        Self { long_short }
+    }
+}
+
+impl PortfolioConstructor for ScoreProportionalConstructor {
+    fn construct(&self, scores: &[f64], _context: &PortfolioContext<'_>) -> Vec<f64> {
+        let raw: Vec<f64> = scores
+            .iter()
+            .map(|&s| if s.is_finite() { s } else { 0.0 })
+            .collect();
+
+        normalize_books(raw, self.long_short)
+    }
+
+    fn name(&self) -> &str {
+        "score_proportional"
+    }
+}
+
+/// Weights linear in cross-sectional rank.
+///
+/// Long-short: weights are proportional to the demeaned rank, so the best name
+/// has the largest long weight and the worst the largest short weight. Each
+/// book is scaled to a gross exposure of 1. Long-only: weights are proportional
+/// to rank (worst name gets the smallest positive weight) and sum to 1.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RankLinearConstructor {
+    /// Whether to hold a short book
+    pub long_short: bool,
+}
+
+impl RankLinearConstructor {
+    /// Create a rank-linear constructor.
+    pub const fn new(long_short: bool) -> Self {
+        Self { long_short }
+    }
+}
+
+impl PortfolioConstructor for RankLinearConstructor {
+    fn construct(&self, scores: &[f64], _context: &PortfolioContext<'_>) -> Vec<f64> {
+        let ranked = ranked_desc(scores);
+        let n = ranked.len() as f64;
+        let mut raw = vec![0.0; scores.len()];
+
+        for (position, &idx) in ranked.iter().enumerate() {
+            // Rank 1 is the worst name, n the best
+            let rank = n - position as f64;
+            raw[idx] = if self.long_short {
+                rank - (n + 1.0) / 2.0
+            } else {
+                rank
+            };
+        }
+
+        normalize_books(raw, self.long_short)
+    }
+
+    fn name(&self) -> &str {
+        "rank_linear"
+    }
+}
+
+/// Equal-weight top and bottom quantile portfolios.
+///
+/// Assets are sorted into `n_quantiles` buckets by score. The top bucket is
+/// held long (summing to 1) and, for long-short, the bottom bucket is held
+/// short (summing to -1). Use 5 for quintiles or 10 for deciles.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantileConstructor {
+    /// Number of quantile buckets
+    pub n_quantiles: usize,
+    /// Whether to short the bottom quantile
+    pub long_short: bool,
+}
+
+impl QuantileConstructor {
+    /// Create a quantile constructor.
+    pub const fn new(n_quantiles: usize, long_short: bool) -> Self {
+        Self {
+            n_quantiles,
+            long_short,
+        }
+    }
+}
+
+impl Default for QuantileConstructor {
+    fn default() -> Self {
+        Self::new(5, true)
+    }
+}
+
+impl PortfolioConstructor for QuantileConstructor {
+    fn construct(&self, scores: &[f64], _context: &PortfolioContext<'_>) -> Vec<f64> {
+        let ranked = ranked_desc(scores);
+        let mut positions = vec![0.0; scores.len()];
+
+        if ranked.is_empty() || self.n_quantiles == 0 {
+            return positions;
+        }
+
+        let bucket = (ranked.len() / self.n_quantiles).max(1);
+        let weight = 1.0 / bucket as f64;
+
+        for &idx in ranked.iter().take(bucket) {
+            positions[idx] = weight;
+        }
+
+        if self.long_short && ranked.len() > bucket {
+            let n_short = bucket.min(ranked.len() - bucket);
+            let short_weight = -1.0 / n_short as f64;
+            for &idx in ranked.iter().skip(ranked.len() - n_short) {
+                positions[idx] = short_weight;
+            }
+        }
+
+        positions
+    }
+
+    fn name(&self) -> &str {
+        "quantile"
+    }
+}
+
+/// Top-N / bottom-N baskets weighted by inverse trailing volatility.
+///
+/// Names are selected exactly as in [`TopBottomConstructor`], then weighted in
+/// proportion to `1 / sigma`, where `sigma` is the standard deviation of the
+/// name's last `lookback` returns. Names without enough history are assigned
+/// the average volatility of their book.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InverseVolatilityConstructor {
+    /// Number of trailing periods used to estimate volatility
+    pub lookback: usize,
+    /// Number of long positions (defaults to half the universe, or all of it when long-only)
+    pub n_long: Option<usize>,
+    /// Number of short positions (defaults to half the universe)
+    pub n_short: Option<usize>,
+    /// Whether to hold a short book
+    pub long_short: bool,
+}
+
+impl InverseVolatilityConstructor {
+    /// Create an inverse-volatility constructor.
+    pub const fn new(
+        lookback: usize,
+        n_long: Option<usize>,
+        n_short: Option<usize>,
+        long_short: bool,
+    ) -> Self {
+        Self {
+            lookback,
+            n_long,
+            n_short,
+            long_short,
+        }
+    }
+
+    /// Trailing volatility of one asset, or NaN if there is too little history.
+    fn volatility(&self, history: &[Vec<f64>], asset: usize) -> f64 {
+        let start = history.len().saturating_sub(self.lookback);
+        let values: Vec<f64> = history[start..]
+            .iter()
+            .filter_map(|row| row.get(asset).copied())
+            .filter(|r| r.is_finite())
+            .collect();
+
+        let std = std_dev(&values);
+        if std > 0.0 { std } else { f64::NAN }
+    }
+}
+
+impl Default for InverseVolatilityConstructor {
+    fn default() -> Self {
+        let config = BacktestConfig::default();
+        Self::new(63, config.n_long, config.n_short, config.long_short)
+    }
+}
+
+impl PortfolioConstructor for InverseVolatilityConstructor {
+    fn construct(&self, scores: &[f64], context: &PortfolioContext<'_>) -> Vec<f64> {
+        let baskets = TopBottomConstructor::new(self.n_long, self.n_short, self.long_short)
+            .construct(scores, context);
+        let mut positions = vec![0.0; scores.len()];
+
+        for side in [1.0, -1.0] {
+            let members: Vec<usize> = (0..baskets.len())
+                .filter(|&i| baskets[i] * side > 0.0)
+                .collect();
+            if members.is_empty() {
+                continue;
+            }
+
+            let gross: f64 = members.iter().map(|&i| baskets[i].abs()).sum();
+            let vols: Vec<f64> = members
+                .iter()
+                .map(|&i| self.volatility(context.returns_history, i))
+                .collect();
+            let known: Vec<f64> = vols.iter().copied().filter(|v| v.is_finite()).collect();
+            let fallback = if known.is_empty() {
+                1.0
+            } else {
+                known.iter().sum::<f64>() / known.len() as f64
+            };
+
+            let inverse: Vec<f64> = vols
+                .iter()
+                .map(|&v| 1.0 / if v.is_finite() { v } else { fallback })
+                .collect();
+            let total: f64 = inverse.iter().sum();
+
+            for (&i, inv) in members.iter().zip(inverse.iter()) {
+                positions[i] = side * gross * inv / total;
+            }
+        }
+
+        positions
+    }
+
+    fn name(&self) -> &str {
+        "inverse_volatility"
+    }
+}
+
+/// Dollar-neutral weights proportional to cross-sectional z-scores.
+///
+/// Scores are standardized across all assets with a finite score and each
+/// weight is set to `z / (sum |z| / 2)`, giving a long book of 1, a short book
+/// of -1 and zero net exposure.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ZScoreConstructor;
+
+impl ZScoreConstructor {
+    /// Create a z-score constructor.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl PortfolioConstructor for ZScoreConstructor {
+    fn construct(&self, scores: &[f64], _context: &PortfolioContext<'_>) -> Vec<f64> {
+        let finite: Vec<f64> = scores.iter().copied().filter(|s| s.is_finite()).collect();
+        let mut positions = vec![0.0; scores.len()];
+
+        if finite.len() < 2 {
+            return positions;
+        }
+
+        let (mean, std) = (mean(&finite), std_dev(&finite));
+
+        if std <= 0.0 {
+            return positions;
+        }
+
+        let z: Vec<f64> = scores
+            .iter()
+            .map(|&s| if s.is_finite() { (s - mean) / std } else { 0.0 })
+            .collect();
+        let half_gross = z.iter().map(|v| v.abs()).sum::<f64>() / 2.0;
+
+        if half_gross > 0.0 {
+            for (p, v) in positions.iter_mut().zip(z.iter()) {
+                *p = v / half_gross;
+            }
+        }
+
+        positions
+    }
+
+    fn name(&self) -> &str {
+        "zscore"
+    }
+}
+
+/// Indices of assets with finite scores, sorted by score descending.
+fn ranked_desc(scores: &[f64]) -> Vec<usize> {
+    let mut indexed: Vec<(usize, f64)> = scores
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &s)| if s.is_finite() { Some((i, s)) } else { None })
+        .collect();
+
+    indexed.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    indexed.into_iter().map(|(i, _)| i).collect()
+}
+
+/// Scale positive raw weights to sum to 1 and, for long-short, negative raw
+/// weights to sum to -1. Long-only drops the negative side.
+fn normalize_books(raw: Vec<f64>, long_short: bool) -> Vec<f64> {
+    let long_total: f64 = raw.iter().filter(|&&w| w > 0.0).sum();
+    let short_total: f64 = raw.iter().filter(|&&w| w < 0.0).map(|w| -w).sum();
+
+    raw.into_iter()
+        .map(|w| {
+            if w > 0.0 && long_total > 0.0 {
+                w / long_total
+            } else if w < 0.0 && long_short && short_total > 0.0 {
+                w / short_total
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMPTY: PortfolioContext<'static> = PortfolioContext::new(&[]);
+
+    fn long_sum(weights: &[f64]) -> f64 {
+        weights.iter().filter(|&&w| w > 0.0).sum()
+    }
+
+    fn short_sum(weights: &[f64]) -> f64 {
+        weights.iter().filter(|&&w| w < 0.0).sum()
+    }
+
+    #[test]
+    fn test_top_bottom_matches_config() {
+        let constructor = TopBottomConstructor::new(Some(2), Some(1), true);
+        let weights = constructor.construct(&[0.5, -0.3, 0.8, -0.6, 0.1], &EMPTY);
+
+        assert_eq!(weights, vec![0.5, 0.0, 0.5, -1.0, 0.0]);
+        assert_eq!(constructor.name(), "top_bottom");
+    }
+
+    #[test]
+    fn test_score_proportional_long_short() {
+        let constructor = ScoreProportionalConstructor::new(true);
+        let weights = constructor.construct(&[3.0, 1.0, -2.0, f64::NAN], &EMPTY);
+
+        assert!((weights[0] - 0.75).abs() < 1e-12);
+        assert!((weights[1] - 0.25).abs() < 1e-12);
+        assert!((weights[2] + 1.0).abs() < 1e-12);
+        assert_eq!(weights[3], 0.0);
+    }
+
+    #[test]
+    fn test_score_proportional_long_only() {
+        let constructor = ScoreProportionalConstructor::new(false);
+        let weights = constructor.construct(&[3.0, 1.0, -2.0], &EMPTY);
+
+        assert!((long_sum(&weights) - 1.0).abs() < 1e-12);
+        assert_eq!(weights[2], 0.0);
+    }
+
+    #[test]
+    fn test_rank_linear_long_short() {
+        let constructor = RankLinearConstructor::new(true);
+        let weights = constructor.construct(&[0.1, 0.4, 0.3, 0.2, 0.0], &EMPTY);
+
+        // Ranks 2,5,4,3,1 demeaned to -1,2,1,0,-2
+        assert!((weights[1] - 2.0 / 3.0).abs() < 1e-12);
+        assert!((weights[2] - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(weights[3], 0.0);
+        assert!((weights[4] + 2.0 / 3.0).abs() < 1e-12);
+        assert!((long_sum(&weights) - 1.0).abs() < 1e-12);
+        assert!((short_sum(&weights) + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rank_linear_long_only() {
+        let constructor = RankLinearConstructor::new(false);
+        let weights = constructor.construct(&[0.3, 0.1, 0.2], &EMPTY);
+
+        assert!((weights[0] - 0.5).abs() < 1e-12);
+        assert!((weights[2] - 1.0 / 3.0).abs() < 1e-12);
+        assert!((weights[1] - 1.0 / 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_quantile_deciles() {
+        let constructor = QuantileConstructor::new(10, true);
+        let scores: Vec<f64> = (0..20).map(|i| i as f64).collect();
+        let weights = constructor.construct(&scores, &EMPTY);
+
+        assert_eq!(weights.iter().filter(|&&w| w > 0.0).count(), 2);
+        assert_eq!(weights.iter().filter(|&&w| w < 0.0).count(), 2);
+        assert!(weights[19] > 0.0 && weights[18] > 0.0);
+        assert!(weights[0] < 0.0 && weights[1] < 0.0);
+        assert!((long_sum(&weights) - 1.0).abs() < 1e-12);
+        assert!((short_sum(&weights) + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_quantile_small_universe() {
+        let constructor = QuantileConstructor::new(5, true);
+        let weights = constructor.construct(&[1.0, 2.0], &EMPTY);
+
+        assert_eq!(weights, vec![-1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_inverse_volatility_weights_calm_names_more() {
+        let constructor = InverseVolatilityConstructor::new(10, Some(2), Some(0), false);
+        let history: Vec<Vec<f64>> = (0..10)
+            .map(|i| {
+                let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+                vec![0.01 * sign, 0.03 * sign, 0.0]
+            })
+            .collect();
+
+        let weights = constructor.construct(&[1.0, 0.9, -1.0], &PortfolioContext::new(&history));
+
+        assert!((long_sum(&weights) - 1.0).abs() < 1e-12);
+        assert!((weights[0] - 0.75).abs() < 1e-9);
+        assert!((weights[1] - 0.25).abs() < 1e-9);
+        assert_eq!(weights[2], 0.0);
+    }
+
+    #[test]
+    fn test_inverse_volatility_without_history() {
+        let constructor = InverseVolatilityConstructor::new(10, Some(2), Some(2), true);
+        let weights = constructor.construct(&[0.4, 0.3, 0.2, 0.1], &EMPTY);
+
+        // Falls back to equal weights
+        assert_eq!(weights, vec![0.5, 0.5, -0.5, -0.5]);
+    }
+
+    #[test]
+    fn test_zscore_dollar_neutral() {
+        let weights = ZScoreConstructor::new().construct(&[1.0, 2.0, 3.0, 6.0, f64::NAN], &EMPTY);
+
+        let net: f64 = weights.iter().sum();
+        assert!(net.abs() < 1e-12);
+        assert!((long_sum(&weights) - 1.0).abs() < 1e-12);
+        assert!((short_sum(&weights) + 1.0).abs() < 1e-12);
+        assert_eq!(weights[4], 0.0);
+        assert!(weights[3] > weights[2]);
+    }
+
+    #[test]
+    fn test_zscore_constant_scores() {
+        let weights = ZScoreConstructor::new().construct(&[1.0, 1.0, 1.0], &EMPTY);
+        assert!(weights.iter().all(|&w| w == 0.0));
+    }
+
+    #[test]
+    fn test_constructors_are_object_safe() {
+        let constructors: Vec<Box<dyn PortfolioConstructor>> = vec![
+            Box::new(TopBottomConstructor::default()),
+            Box::new(ScoreProportionalConstructor::new(true)),
+            Box::new(RankLinearConstructor::new(true)),
+            Box::new(QuantileConstructor::default()),
+            Box::new(InverseVolatilityConstructor::default()),
+            Box::new(ZScoreConstructor::new()),
+        ];
+
+        for constructor in &constructors {
+            let weights = constructor.construct(&[0.3, -0.1, 0.2, f64::NAN], &EMPTY);
+            assert_eq!(weights.len(), 4, "{}", constructor.name());
+            assert_eq!(weights[3], 0.0, "{}", constructor.name());
+        }
+    }
+}
@@ -0,0 +1,499 @@
+//! Quantile portfolio analysis.
+//!
+//! Sorts assets into quantiles by signal score every period and tracks the
+//! equal-weighted forward return of each bucket. A good signal produces
+//! bucket returns that rise steadily from the bottom to the top quantile and
+//! a persistent top-minus-bottom spread.
+//!
+//! Forward returns follow the backtest convention: `returns[i]` is the
+//! return earned by positions formed from the scores at period `i` (as in
+//! `Panel::forward_returns`), so scores at `i` are paired with
+//! `returns[i..i + horizon]`, compounded over the horizon.
+
+use chrono::NaiveDate;
+use ndarray::Array1;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use tarifa_traits::{Result, TarifaError};
+
+use crate::ic::calculate_ic;
+use crate::metrics::{HacLag, t_stat};
+use crate::stats::finite_mean;
+
+/// Quantile analysis configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantileConfig {
+    /// Number of quantile buckets
+    pub n_quantiles: usize,
+    /// Forward return horizons (in periods)
+    pub horizons: Vec<usize>,
+}
+
+impl Default for QuantileConfig {
+    fn default() -> Self {
+        Self {
+            n_quantiles: 5,
+            horizons: vec![1, 5, 10],
+        }
+    }
+}
+
+/// Quantile returns at one forward horizon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantileReturns {
+    /// Forward return horizon (in periods)
+    pub horizon: usize,
+    /// Mean forward return per quantile, bottom quantile first
+    pub mean_returns: Vec<f64>,
+    /// Top-minus-bottom forward return per formation period
+    pub spread: Vec<f64>,
+    /// Mean of the finite spread observations
+    pub mean_spread: f64,
+    /// Newey-West t-statistic of the mean spread, with `horizon - 1` lags to
+    /// account for overlapping observations
+    pub spread_t_stat: f64,
+    /// Spearman correlation between quantile rank and mean return, from -1
+    /// (perfectly inverted) to 1 (perfectly monotonic)
+    pub monotonicity: f64,
+}
+
+/// Quantile portfolio analysis of a signal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantileAnalysis {
+    /// Number of quantile buckets
+    pub n_quantiles: usize,
+    /// Formation date of each one-period quantile return
+    pub dates: Vec<NaiveDate>,
+    /// One-period return per quantile (periods x quantiles), NaN where a
+    /// period has too few assets to fill every bucket
+    pub period_returns: Vec<Vec<f64>>,
+    /// Forward returns at each configured horizon
+    pub horizons: Vec<QuantileReturns>,
+}
+
+impl QuantileAnalysis {
+    /// Sort assets into quantiles by score and measure forward returns.
+    ///
+    /// Each period, assets with a finite score and forward return are ranked
+    /// by score and split into `n_quantiles` equally sized buckets; quantile
+    /// 0 holds the lowest scores. Periods with fewer such assets than
+    /// buckets are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal_scores` - Signal scores (periods x assets)
+    /// * `returns` - Asset returns (periods x assets)
+    /// * `dates` - Trading dates
+    /// * `config` - Quantile configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if fewer than two quantiles or a zero horizon are
+    /// requested, the inputs differ in length, or there are fewer than two
+    /// periods.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::{QuantileAnalysis, QuantileConfig};
+    ///
+    /// let analysis = QuantileAnalysis::compute(&scores, &returns, &dates, &QuantileConfig {
+    ///     n_quantiles: 10,
+    ///     ..Default::default()
+    /// })?;
+    /// for q in &analysis.horizons {
+    ///     println!("{}d spread {:.4} (t = {:.2})", q.horizon, q.mean_spread, q.spread_t_stat);
+    /// }
+    /// ```
+    pub fn compute(
+        signal_scores: &[Vec<f64>],
+        returns: &[Vec<f64>],
+        dates: &[NaiveDate],
+        config: &QuantileConfig,
+    ) -> Result<Self> {
+        let n_quantiles = config.n_quantiles;
+        if n_quantiles < 2 {
+            return Err(TarifaError::InvalidData(format!(
+                "quantile analysis needs at least 2 quantiles, got {n_quantiles}"
+            )));
+        }
+        if config.horizons.contains(&0) {
+            return Err(TarifaError::InvalidData(
+                "quantile horizons must be positive".to_string(),
+            ));
+        }
+        if signal_scores.len() != returns.len() || returns.len() != dates.len() {
+            return Err(TarifaError::InvalidData(format!(
+                "scores ({}), returns ({}) and dates ({}) must have the same length",
+                signal_scores.len(),
+                returns.len(),
+                dates.len()
+            )));
+        }
+        if returns.len() < 2 {
+            return Err(TarifaError::InsufficientData(format!(
+                "quantile analysis needs at least 2 periods, got {}",
+                returns.len()
+            )));
+        }
+
+        let period_returns = quantile_returns(signal_scores, returns, 1, n_quantiles);
+        let horizons = config
+            .horizons
+            .iter()
+            .map(|&horizon| {
+                let bucket_returns = if horizon == 1 {
+                    period_returns.clone()
+                } else {
+                    quantile_returns(signal_scores, returns, horizon, n_quantiles)
+                };
+                summarize(horizon, &bucket_returns, n_quantiles)
+            })
+            .collect();
+
+        Ok(Self {
+            n_quantiles,
+            dates: dates.to_vec(),
+            period_returns,
+            horizons,
+        })
+    }
+
+    /// Results at a specific horizon, if it was analyzed.
+    pub fn at_horizon(&self, horizon: usize) -> Option<&QuantileReturns> {
+        self.horizons.iter().find(|q| q.horizon == horizon)
+    }
+
+    /// Compounded one-period return of each quantile (periods x quantiles).
+    ///
+    /// Skipped periods contribute no return.
+    pub fn cumulative_returns(&self) -> Vec<Vec<f64>> {
+        let mut growth = vec![1.0; self.n_quantiles];
+        self.period_returns
+            .iter()
+            .map(|row| {
+                growth
+                    .iter_mut()
+                    .zip(row)
+                    .map(|(g, &r)| {
+                        if r.is_finite() {
+                            *g *= 1.0 + r;
+                        }
+                        *g - 1.0
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Cumulative quantile returns as a DataFrame.
+    ///
+    /// Columns are `date`, `q1` (lowest scores) through `qN`, and `spread`,
+    /// the cumulative return of holding the top quantile against the
+    /// bottom one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the DataFrame cannot be built.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let df = analysis.to_frame()?;
+    /// println!("{df}");
+    /// ```
+    pub fn to_frame(&self) -> Result<DataFrame> {
+        let cumulative = self.cumulative_returns();
+
+        let mut columns = vec![Column::new("date".into(), &self.dates)];
+        for q in 0..self.n_quantiles {
+            let values: Vec<f64> = cumulative.iter().map(|row| row[q]).collect();
+            columns.push(Column::new(format!("q{}", q + 1).into(), values));
+        }
+
+        let mut growth = 1.0;
+        let spread: Vec<f64> = self
+            .period_returns
+            .iter()
+            .map(|row| {
+                let r = row[self.n_quantiles - 1] - row[0];
+                if r.is_finite() {
+                    growth *= 1.0 + r;
+                }
+                growth - 1.0
+            })
+            .collect();
+        columns.push(Column::new("spread".into(), spread));
+
+        Ok(DataFrame::new(columns)?)
+    }
+}
+
+/// Equal-weighted forward return per quantile for every formation period
+/// with a full `horizon` of returns ahead of it.
+fn quantile_returns(
+    signal_scores: &[Vec<f64>],
+    returns: &[Vec<f64>],
+    horizon: usize,
+    n_quantiles: usize,
+) -> Vec<Vec<f64>> {
+    let n_periods = (returns.len() + 1).saturating_sub(horizon);
+
+    (0..n_periods)
+        .map(|i| {
+            let scores = &signal_scores[i];
+            let mut pairs: Vec<(f64, f64)> = (0..scores.len())
+                .filter_map(|j| {
+                    let forward = forward_return(&returns[i..i + horizon], j);
+                    (scores[j].is_finite() && forward.is_finite()).then_some((scores[j], forward))
+                })
+                .collect();
+
+            let n = pairs.len();
+            if n < n_quantiles {
+                return vec![f64::NAN; n_quantiles];
+            }
+            pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut sums = vec![0.0; n_quantiles];
+            let mut counts = vec![0usize; n_quantiles];
+            for (rank, &(_, r)) in pairs.iter().enumerate() {
+                let q = rank * n_quantiles / n;
+                sums[q] += r;
+                counts[q] += 1;
+            }
+            sums.iter()
+                .zip(&counts)
+                .map(|(s, &c)| s / c as f64)
+                .collect()
+        })
+        .collect()
+}
+
+/// Compounded return of asset `j` over consecutive periods.
+fn forward_return(returns: &[Vec<f64>], j: usize) -> f64 {
+    returns
+        .iter()
+        .map(|row| row.get(j).copied().unwrap_or(f64::NAN))
+        .fold(1.0, |g, r| g * (1.0 + r))
+        - 1.0
+}
+
+fn summarize(horizon: usize, bucket_returns: &[Vec<f64>], n_quantiles: usize) -> QuantileReturns {
+    let mean_returns: Vec<f64> = (0..n_quantiles)
+        .map(|q| finite_mean(&bucket_returns.iter().map(|row| row[q]).collect::<Vec<_>>()))
+        .collect();
+    let spread: Vec<f64> = bucket_returns
+        .iter()
+        .map(|row| row[n_quantiles - 1] - row[0])
+        .collect();
+
+    let ranks = Array1::from_iter((0..n_quantiles).map(|q| q as f64));
+    let monotonicity = calculate_ic(&ranks, &Array1::from_vec(mean_returns.clone()));
+
+    let mean_spread = finite_mean(&spread);
+    let n_spread = spread.iter().filter(|x| x.is_finite()).count();
+    let spread_t_stat = t_stat(&spread, Some(HacLag::Horizon(horizon).lags(n_spread)));
+
+    QuantileReturns {
+        horizon,
+        mean_returns,
+        spread,
+        mean_spread,
+        spread_t_stat,
+        monotonicity,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::performance::daily_dates;
+    use crate::rng::SplitMix64;
+
+    /// Ten assets whose return over each period is proportional to their
+    /// score at the start of it, plus noise that alternates in sign.
+    fn predictive_panel(n_periods: usize) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+        let scores: Vec<Vec<f64>> = (0..n_periods)
+            .map(|t| (0..10).map(|j| ((j + t) % 10) as f64).collect())
+            .collect();
+        let returns: Vec<Vec<f64>> = (0..n_periods)
+            .map(|t| {
+                (0..10)
+                    .map(|j| {
+                        let signal = scores[t][j] * 0.001;
+                        let noise = if (t + j) % 2 == 0 { 0.002 } else { -0.002 };
+                        signal + noise
+                    })
+                    .collect()
+            })
+            .collect();
+        (scores, returns)
+    }
+
+    #[test]
+    fn test_predictive_signal_is_monotonic() {
+        let (scores, returns) = predictive_panel(60);
+        let analysis = QuantileAnalysis::compute(
+            &scores,
+            &returns,
+            &daily_dates(60),
+            &QuantileConfig {
+                n_quantiles: 5,
+                horizons: vec![1],
+            },
+        )
+        .unwrap();
+
+        let q = analysis.at_horizon(1).unwrap();
+        assert_eq!(q.mean_returns.len(), 5);
+        assert!(q.mean_returns.windows(2).all(|w| w[0] < w[1]));
+        assert!((q.monotonicity - 1.0).abs() < 1e-12);
+        // Top bucket holds scores 8-9, bottom 0-1: spread of 80bp each period
+        assert!((q.mean_spread - 0.008).abs() < 1e-12);
+        assert_eq!(q.spread.len(), 60);
+    }
+
+    #[test]
+    fn test_spread_t_stat() {
+        let (scores, mut returns) = predictive_panel(60);
+        let mut rng = SplitMix64::new(7);
+        for row in &mut returns {
+            for r in row.iter_mut() {
+                *r += 0.002 * rng.next_normal();
+            }
+        }
+        let analysis = QuantileAnalysis::compute(
+            &scores,
+            &returns,
+            &daily_dates(60),
+            &QuantileConfig::default(),
+        )
+        .unwrap();
+
+        let q = analysis.at_horizon(1).unwrap();
+        assert!(q.spread_t_stat > 5.0);
+
+        // Inverted signal flips the spread and monotonicity
+        let inverted: Vec<Vec<f64>> = scores
+            .iter()
+            .map(|row| row.iter().map(|s| -s).collect())
+            .collect();
+        let analysis = QuantileAnalysis::compute(
+            &inverted,
+            &returns,
+            &daily_dates(60),
+            &QuantileConfig::default(),
+        )
+        .unwrap();
+        let q = analysis.at_horizon(1).unwrap();
+        assert!(q.mean_spread < 0.0);
+        assert!((q.monotonicity + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_multi_horizon_compounding() {
+        let scores = vec![vec![1.0, 2.0]; 4];
+        let returns = vec![vec![0.01, 0.02]; 4];
+        let analysis = QuantileAnalysis::compute(
+            &scores,
+            &returns,
+            &daily_dates(4),
+            &QuantileConfig {
+                n_quantiles: 2,
+                horizons: vec![1, 2],
+            },
+        )
+        .unwrap();
+
+        let two = analysis.at_horizon(2).unwrap();
+        assert_eq!(two.spread.len(), 3);
+        assert!((two.mean_returns[0] - (1.01_f64.powi(2) - 1.0)).abs() < 1e-12);
+        assert!((two.mean_returns[1] - (1.02_f64.powi(2) - 1.0)).abs() < 1e-12);
+        assert!(analysis.at_horizon(5).is_none());
+    }
+
+    #[test]
+    fn test_cumulative_frame() {
+        let scores = vec![vec![1.0, 2.0]; 3];
+        let returns = vec![vec![0.01, 0.02], vec![0.01, 0.02], vec![0.0, 0.0]];
+        let analysis = QuantileAnalysis::compute(
+            &scores,
+            &returns,
+            &daily_dates(3),
+            &QuantileConfig {
+                n_quantiles: 2,
+                horizons: vec![1],
+            },
+        )
+        .unwrap();
+
+        let cumulative = analysis.cumulative_returns();
+        assert!((cumulative[1][1] - (1.02_f64.powi(2) - 1.0)).abs() < 1e-12);
+
+        let df = analysis.to_frame().unwrap();
+        assert_eq!(df.height(), 3);
+        assert_eq!(
+            df.get_column_names_str(),
+            ["date", "q1", "q2", "spread"].to_vec()
+        );
+        let spread = df.column("spread").unwrap().f64().unwrap().get(1).unwrap();
+        assert!((spread - (1.01_f64.powi(2) - 1.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_thin_periods_skipped() {
+        let scores = vec![
+            vec![1.0, f64::NAN, f64::NAN],
+            vec![1.0, 2.0, 3.0],
+            vec![1.0, 2.0, 3.0],
+        ];
+        let returns = vec![vec![0.0; 3], vec![0.01, 0.02, 0.03], vec![0.01, 0.02, 0.03]];
+        let analysis = QuantileAnalysis::compute(
+            &scores,
+            &returns,
+            &daily_dates(3),
+            &QuantileConfig {
+                n_quantiles: 3,
+                horizons: vec![1],
+            },
+        )
+        .unwrap();
+
+        assert!(analysis.period_returns[0].iter().all(|r| r.is_nan()));
+        let q = analysis.at_horizon(1).unwrap();
+        assert!((q.mean_returns[2] - 0.03).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let scores = vec![vec![1.0, 2.0]; 3];
+        let returns = vec![vec![0.0, 0.0]; 3];
+        let config = QuantileConfig {
+            n_quantiles: 1,
+            horizons: vec![1],
+        };
+        assert!(matches!(
+            QuantileAnalysis::compute(&scores, &returns, &daily_dates(3), &config),
+            Err(TarifaError::InvalidData(_))
+        ));
+        assert!(matches!(
+            QuantileAnalysis::compute(
+                &scores,
+                &returns,
+                &daily_dates(2),
+                &QuantileConfig::default()
+            ),
+            Err(TarifaError::InvalidData(_))
+        ));
+        assert!(matches!(
+            QuantileAnalysis::compute(
+                &scores[..1],
+                &returns[..1],
+                &daily_dates(1),
+                &QuantileConfig::default()
+            ),
+            Err(TarifaError::InsufficientData(_))
+        ));
+    }
+}
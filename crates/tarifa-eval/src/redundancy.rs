@@ -0,0 +1,417 @@
+//! Redundancy analysis across signals.
+//!
+//! Before signals are combined it pays to know how much they overlap. This
+//! module measures overlap three ways: how similarly the signals rank assets
+//! each period, how similarly their ICs move over time, and how much IC each
+//! signal keeps once the others are controlled for. Signals are also
+//! clustered hierarchically on their rank correlations.
+//!
+//! Inputs follow [`ic_series`](crate::ic::ic_series): `forward_returns[i]` is
+//! the return that follows each signal's scores at period `i`.
+
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+use tarifa_traits::{Result, TarifaError};
+
+use crate::ic::{calculate_ic, compute_ranks, pearson_correlation};
+use crate::linalg::least_squares;
+use crate::stats::finite_mean;
+
+/// Linkage criterion for hierarchical clustering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Linkage {
+    /// Distance between the closest members
+    Single,
+    /// Distance between the farthest members
+    Complete,
+    /// Mean distance between members
+    #[default]
+    Average,
+}
+
+/// One merge step of a hierarchical clustering.
+///
+/// Clusters are numbered like SciPy linkage matrices: signals are clusters
+/// `0..n`, and the cluster created by merge `k` is `n + k`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ClusterMerge {
+    /// First merged cluster
+    pub left: usize,
+    /// Second merged cluster
+    pub right: usize,
+    /// Linkage distance at which they merge
+    pub distance: f64,
+    /// Number of signals in the merged cluster
+    pub size: usize,
+}
+
+/// Redundancy of a set of signals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedundancyAnalysis {
+    /// Signal names
+    pub names: Vec<String>,
+    /// Time-averaged cross-sectional Spearman correlation between signals
+    pub rank_correlation: Vec<Vec<f64>>,
+    /// Pearson correlation between the signals' IC series
+    pub ic_correlation: Vec<Vec<f64>>,
+    /// Mean IC of each signal
+    pub mean_ic: Vec<f64>,
+    /// Mean IC of each signal after regressing out the other signals each period
+    pub incremental_ic: Vec<f64>,
+    /// Hierarchical clustering on the distance `1 - |rank correlation|`
+    pub merges: Vec<ClusterMerge>,
+}
+
+impl RedundancyAnalysis {
+    /// Measure how redundant several signals are.
+    ///
+    /// Incremental IC is computed on cross-sectional ranks: each period, a
+    /// signal's score ranks are regressed on the ranks of every other signal
+    /// and the residual is correlated with forward returns. A signal whose
+    /// incremental IC is near zero adds little to the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - Signal names
+    /// * `signal_scores` - Score panel of each signal (signals x periods x assets)
+    /// * `forward_returns` - Forward returns (periods x assets)
+    /// * `linkage` - Linkage criterion for the clustering
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if fewer than two signals are given or the names and
+    /// panels do not match.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::{Linkage, RedundancyAnalysis};
+    ///
+    /// let analysis = RedundancyAnalysis::compute(&names, &panels, &forward_returns, Linkage::Average)?;
+    /// for group in analysis.clusters(0.5) {
+    ///     println!("{:?}", group.iter().map(|&i| &analysis.names[i]).collect::<Vec<_>>());
+    /// }
+    /// ```
+    pub fn compute(
+        names: &[String],
+        signal_scores: &[Vec<Array1<f64>>],
+        forward_returns: &[Array1<f64>],
+        linkage: Linkage,
+    ) -> Result<Self> {
+        let n_signals = signal_scores.len();
+        if n_signals < 2 {
+            return Err(TarifaError::InsufficientData(format!(
+                "redundancy analysis needs at least 2 signals, got {n_signals}"
+            )));
+        }
+        if names.len() != n_signals {
+            return Err(TarifaError::InvalidData(format!(
+                "{} names for {n_signals} signals",
+                names.len()
+            )));
+        }
+
+        let n_periods = signal_scores
+            .iter()
+            .map(Vec::len)
+            .min()
+            .unwrap_or(0)
+            .min(forward_returns.len());
+
+        let ic_series: Vec<Vec<f64>> = signal_scores
+            .iter()
+            .map(|panel| {
+                (0..n_periods)
+                    .map(|t| calculate_ic(&panel[t], &forward_returns[t]))
+                    .collect()
+            })
+            .collect();
+
+        let mut rank_correlation = vec![vec![1.0; n_signals]; n_signals];
+        let mut ic_correlation = vec![vec![1.0; n_signals]; n_signals];
+        for a in 0..n_signals {
+            for b in a + 1..n_signals {
+                let per_period: Vec<f64> = (0..n_periods)
+                    .map(|t| calculate_ic(&signal_scores[a][t], &signal_scores[b][t]))
+                    .collect();
+                let rank = finite_mean(&per_period);
+                let (x, y): (Vec<f64>, Vec<f64>) = ic_series[a]
+                    .iter()
+                    .zip(&ic_series[b])
+                    .filter(|(x, y)| x.is_finite() && y.is_finite())
+                    .unzip();
+                let ic = pearson_correlation(&x, &y);
+                rank_correlation[a][b] = rank;
+                rank_correlation[b][a] = rank;
+                ic_correlation[a][b] = ic;
+                ic_correlation[b][a] = ic;
+            }
+        }
+
+        let mut residual_ic = vec![Vec::with_capacity(n_periods); n_signals];
+        for t in 0..n_periods {
+            let period: Vec<&Array1<f64>> = signal_scores.iter().map(|p| &p[t]).collect();
+            for (k, ic) in incremental_ics(&period, &forward_returns[t])
+                .into_iter()
+                .enumerate()
+            {
+                residual_ic[k].push(ic);
+            }
+        }
+
+        let distances: Vec<Vec<f64>> = rank_correlation
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|rho| {
+                        if rho.is_finite() {
+                            1.0 - rho.abs()
+                        } else {
+                            1.0
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self {
+            names: names.to_vec(),
+            mean_ic: ic_series.iter().map(|ic| finite_mean(ic)).collect(),
+            incremental_ic: residual_ic.iter().map(|ic| finite_mean(ic)).collect(),
+            merges: cluster(distances, linkage),
+            rank_correlation,
+            ic_correlation,
+        })
+    }
+
+    /// Groups of signals that merge at or below `max_distance`.
+    ///
+    /// Distances are `1 - |rank correlation|`, so `max_distance = 0.3` groups
+    /// signals whose linked rank correlations are at least 0.7 in absolute
+    /// value. Groups are sorted by their first signal.
+    pub fn clusters(&self, max_distance: f64) -> Vec<Vec<usize>> {
+        let n = self.names.len();
+        let mut members: Vec<Option<Vec<usize>>> = (0..n).map(|i| Some(vec![i])).collect();
+
+        for merge in &self.merges {
+            if merge.distance > max_distance {
+                members.push(None);
+                continue;
+            }
+            let mut merged = members[merge.left].take().unwrap_or_default();
+            merged.extend(members[merge.right].take().unwrap_or_default());
+            merged.sort_unstable();
+            members.push(Some(merged));
+        }
+
+        let mut groups: Vec<Vec<usize>> = members.into_iter().flatten().collect();
+        groups.sort();
+        groups
+    }
+}
+
+/// IC of each signal's rank residual after regressing on the other signals.
+fn incremental_ics(scores: &[&Array1<f64>], forward_returns: &Array1<f64>) -> Vec<f64> {
+    let n_signals = scores.len();
+    let n_assets = scores
+        .iter()
+        .map(|s| s.len())
+        .min()
+        .unwrap_or(0)
+        .min(forward_returns.len());
+
+    let rows: Vec<usize> = (0..n_assets)
+        .filter(|&j| forward_returns[j].is_finite() && scores.iter().all(|s| s[j].is_finite()))
+        .collect();
+    if rows.len() <= n_signals + 1 {
+        return vec![f64::NAN; n_signals];
+    }
+
+    let ranks: Vec<Vec<f64>> = scores
+        .iter()
+        .map(|s| compute_ranks(&rows.iter().map(|&j| s[j]).collect::<Vec<_>>()))
+        .collect();
+    let returns = Array1::from_iter(rows.iter().map(|&j| forward_returns[j]));
+
+    (0..n_signals)
+        .map(|k| {
+            let design: Vec<Vec<f64>> = (0..rows.len())
+                .map(|r| {
+                    std::iter::once(1.0)
+                        .chain((0..n_signals).filter(|&m| m != k).map(|m| ranks[m][r]))
+                        .collect()
+                })
+                .collect();
+            let Some(beta) = least_squares(&design, &ranks[k]) else {
+                return f64::NAN;
+            };
+            let residual = Array1::from_iter(
+                design
+                    .iter()
+                    .zip(&ranks[k])
+                    .map(|(x, &y)| y - x.iter().zip(&beta).map(|(a, b)| a * b).sum::<f64>()),
+            );
+            calculate_ic(&residual, &returns)
+        })
+        .collect()
+}
+
+/// Agglomerative clustering of a symmetric distance matrix.
+fn cluster(mut distances: Vec<Vec<f64>>, linkage: Linkage) -> Vec<ClusterMerge> {
+    let n = distances.len();
+    // Active clusters: (cluster id, size); row/column i of `distances`
+    let mut active: Vec<(usize, usize)> = (0..n).map(|i| (i, 1)).collect();
+    let mut merges = Vec::with_capacity(n.saturating_sub(1));
+
+    while active.len() > 1 {
+        let mut best = (0, 1, f64::INFINITY);
+        for (a, row) in distances.iter().enumerate() {
+            for (b, &d) in row.iter().enumerate().skip(a + 1) {
+                if d < best.2 {
+                    best = (a, b, d);
+                }
+            }
+        }
+        let (a, b, distance) = best;
+        let (size_a, size_b) = (active[a].1, active[b].1);
+
+        // Lance-Williams update: row `a` becomes the merged cluster
+        for c in (0..active.len()).filter(|&c| c != a && c != b) {
+            let (da, db) = (distances[a][c], distances[b][c]);
+            let merged = match linkage {
+                Linkage::Single => da.min(db),
+                Linkage::Complete => da.max(db),
+                Linkage::Average => {
+                    (da * size_a as f64 + db * size_b as f64) / (size_a + size_b) as f64
+                }
+            };
+            distances[a][c] = merged;
+            distances[c][a] = merged;
+        }
+
+        merges.push(ClusterMerge {
+            left: active[a].0,
+            right: active[b].0,
+            distance,
+            size: size_a + size_b,
+        });
+        active[a] = (n + merges.len() - 1, size_a + size_b);
+        active.remove(b);
+        distances.remove(b);
+        for row in &mut distances {
+            row.remove(b);
+        }
+    }
+
+    merges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    type Panels = (Vec<String>, Vec<Vec<Array1<f64>>>, Vec<Array1<f64>>);
+
+    /// Signals: `a`, a noisy copy of `a`, and an independent `c`. Returns
+    /// load on both `a` and `c`.
+    fn panels() -> Panels {
+        let mut rng = SplitMix64::new(5);
+        let (n_periods, n_assets) = (40, 60);
+        let mut a = Vec::new();
+        let mut b = Vec::new();
+        let mut c = Vec::new();
+        let mut returns = Vec::new();
+        for _ in 0..n_periods {
+            let sa: Vec<f64> = (0..n_assets).map(|_| rng.next_normal()).collect();
+            let sb: Vec<f64> = sa.iter().map(|x| x + 0.2 * rng.next_normal()).collect();
+            let sc: Vec<f64> = (0..n_assets).map(|_| rng.next_normal()).collect();
+            let r: Vec<f64> = (0..n_assets)
+                .map(|j| 0.01 * sa[j] + 0.01 * sc[j] + 0.02 * rng.next_normal())
+                .collect();
+            a.push(Array1::from_vec(sa));
+            b.push(Array1::from_vec(sb));
+            c.push(Array1::from_vec(sc));
+            returns.push(Array1::from_vec(r));
+        }
+        let names = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        (names, vec![a, b, c], returns)
+    }
+
+    #[test]
+    fn test_correlations() {
+        let (names, signals, returns) = panels();
+        let analysis =
+            RedundancyAnalysis::compute(&names, &signals, &returns, Linkage::Average).unwrap();
+
+        assert_eq!(analysis.rank_correlation.len(), 3);
+        assert_eq!(analysis.rank_correlation[0][0], 1.0);
+        assert!(analysis.rank_correlation[0][1] > 0.9);
+        assert!(analysis.rank_correlation[0][2].abs() < 0.1);
+        assert_eq!(
+            analysis.rank_correlation[1][0],
+            analysis.rank_correlation[0][1]
+        );
+        assert!(analysis.ic_correlation[0][1] > 0.7);
+        assert!(analysis.mean_ic.iter().all(|&ic| ic > 0.1));
+    }
+
+    #[test]
+    fn test_incremental_ic() {
+        let (names, signals, returns) = panels();
+        let analysis =
+            RedundancyAnalysis::compute(&names, &signals, &returns, Linkage::Average).unwrap();
+
+        // The noisy copy adds nothing once `a` is known; `c` keeps its IC
+        assert!(analysis.incremental_ic[1].abs() < analysis.mean_ic[1] / 3.0);
+        assert!(analysis.incremental_ic[2] > analysis.mean_ic[2] * 0.8);
+    }
+
+    #[test]
+    fn test_clustering() {
+        let (names, signals, returns) = panels();
+        let analysis =
+            RedundancyAnalysis::compute(&names, &signals, &returns, Linkage::Average).unwrap();
+
+        assert_eq!(analysis.merges.len(), 2);
+        assert_eq!((analysis.merges[0].left, analysis.merges[0].right), (0, 1));
+        assert_eq!(analysis.merges[1].size, 3);
+        assert!(analysis.merges[0].distance < analysis.merges[1].distance);
+
+        assert_eq!(analysis.clusters(0.3), vec![vec![0, 1], vec![2]]);
+        assert_eq!(analysis.clusters(1.0), vec![vec![0, 1, 2]]);
+        assert_eq!(analysis.clusters(0.0), vec![vec![0], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn test_linkage_criteria() {
+        let distances = vec![
+            vec![0.0, 0.1, 0.5, 0.9],
+            vec![0.1, 0.0, 0.6, 0.8],
+            vec![0.5, 0.6, 0.0, 0.3],
+            vec![0.9, 0.8, 0.3, 0.0],
+        ];
+        let single = cluster(distances.clone(), Linkage::Single);
+        let complete = cluster(distances.clone(), Linkage::Complete);
+        let average = cluster(distances, Linkage::Average);
+
+        assert_eq!((single[1].left, single[1].right), (2, 3));
+        assert!((single[2].distance - 0.5).abs() < 1e-12);
+        assert!((complete[2].distance - 0.9).abs() < 1e-12);
+        assert!((average[2].distance - 0.7).abs() < 1e-12);
+        assert_eq!(average[2].size, 4);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let (names, signals, returns) = panels();
+        assert!(matches!(
+            RedundancyAnalysis::compute(&names[..1], &signals[..1], &returns, Linkage::Average),
+            Err(TarifaError::InsufficientData(_))
+        ));
+        assert!(matches!(
+            RedundancyAnalysis::compute(&names[..2], &signals, &returns, Linkage::Average),
+            Err(TarifaError::InvalidData(_))
+        ));
+    }
+}
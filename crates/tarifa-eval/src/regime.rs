@@ -0,0 +1,164 @@
+//! Market regime classification.
+//!
+//! Labels each period of a market return series with a regime so that signal
+//! performance can be reported by regime. Labels only use returns up to and
+//! including the labelled period; periods without enough history are left
+//! unlabelled.
+
+use serde::{Deserialize, Serialize};
+
+use crate::stats::std_dev;
+
+/// Built-in market regime definitions.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MarketRegime {
+    /// `"drawdown"` while the market is at least `threshold` below its
+    /// running peak, `"normal"` otherwise
+    Drawdown {
+        /// Drawdown depth as a positive fraction (e.g. 0.1 for 10%)
+        threshold: f64,
+    },
+    /// `"high_vol"` or `"low_vol"` depending on whether trailing volatility
+    /// is above or below its median over all periods so far
+    Volatility {
+        /// Trailing window in periods
+        window: usize,
+    },
+    /// `"up"` or `"down"` by the sign of the trailing compounded return
+    Trend {
+        /// Trailing window in periods
+        window: usize,
+    },
+}
+
+impl MarketRegime {
+    /// Label each period of a market return series.
+    ///
+    /// The volatility split compares each period's trailing volatility with
+    /// the expanding median of trailing volatility up to that period, so
+    /// labels never depend on later returns.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::MarketRegime;
+    ///
+    /// let labels = MarketRegime::Volatility { window: 21 }.labels(&market_returns);
+    /// let by_regime = evaluator.regime_ic(21, &labels);
+    /// ```
+    pub fn labels(&self, market_returns: &[f64]) -> Vec<Option<String>> {
+        match *self {
+            Self::Drawdown { threshold } => {
+                let (mut wealth, mut peak) = (1.0, 1.0_f64);
+                market_returns
+                    .iter()
+                    .map(|r| {
+                        if r.is_finite() {
+                            wealth *= 1.0 + r;
+                            peak = peak.max(wealth);
+                        }
+                        let drawdown = 1.0 - wealth / peak;
+                        let label = if drawdown >= threshold {
+                            "drawdown"
+                        } else {
+                            "normal"
+                        };
+                        Some(label.to_string())
+                    })
+                    .collect()
+            }
+            Self::Volatility { window } => {
+                let mut history: Vec<f64> = Vec::new();
+                trailing(market_returns, window, std_dev)
+                    .into_iter()
+                    .map(|v| {
+                        if !v.is_finite() {
+                            return None;
+                        }
+                        let at = history.partition_point(|h| *h < v);
+                        history.insert(at, v);
+                        let median = history[history.len() / 2];
+                        Some(if v > median { "high_vol" } else { "low_vol" }.to_string())
+                    })
+                    .collect()
+            }
+            Self::Trend { window } => trailing(market_returns, window, |w| {
+                w.iter().fold(1.0, |g, r| g * (1.0 + r)) - 1.0
+            })
+            .into_iter()
+            .map(|r| {
+                r.is_finite()
+                    .then(|| if r >= 0.0 { "up" } else { "down" }.to_string())
+            })
+            .collect(),
+        }
+    }
+}
+
+/// Apply `stat` to each full trailing window; NaN before the first one.
+fn trailing(returns: &[f64], window: usize, stat: impl Fn(&[f64]) -> f64) -> Vec<f64> {
+    (0..returns.len())
+        .map(|i| {
+            if window == 0 || i + 1 < window {
+                return f64::NAN;
+            }
+            let values = &returns[i + 1 - window..=i];
+            if values.iter().all(|r| r.is_finite()) {
+                stat(values)
+            } else {
+                f64::NAN
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drawdown_regime() {
+        let returns = [0.1, -0.05, -0.1, 0.02, 0.2];
+        let labels = MarketRegime::Drawdown { threshold: 0.1 }.labels(&returns);
+
+        let expected = ["normal", "normal", "drawdown", "drawdown", "normal"];
+        for (label, want) in labels.iter().zip(expected) {
+            assert_eq!(label.as_deref(), Some(want));
+        }
+    }
+
+    #[test]
+    fn test_volatility_regime() {
+        let returns = [0.01, -0.01, 0.01, -0.01, 0.05, -0.05, 0.05, -0.05];
+        let labels = MarketRegime::Volatility { window: 2 }.labels(&returns);
+
+        assert_eq!(labels[0], None);
+        assert_eq!(labels[1].as_deref(), Some("low_vol"));
+        assert_eq!(labels[7].as_deref(), Some("high_vol"));
+    }
+
+    #[test]
+    fn test_trend_regime() {
+        let returns = [0.01, 0.02, -0.05, -0.01, 0.03];
+        let labels = MarketRegime::Trend { window: 2 }.labels(&returns);
+
+        assert_eq!(labels[0], None);
+        assert_eq!(labels[1].as_deref(), Some("up"));
+        assert_eq!(labels[2].as_deref(), Some("down"));
+        assert_eq!(labels[3].as_deref(), Some("down"));
+        assert_eq!(labels[4].as_deref(), Some("up"));
+    }
+
+    #[test]
+    fn test_volatility_regime_uses_no_later_returns() {
+        let returns = [0.01, -0.01, 0.03, -0.02, 0.05, -0.05, 0.001, -0.001];
+        let labels = MarketRegime::Volatility { window: 2 }.labels(&returns);
+
+        for n in 2..returns.len() {
+            let prefix = MarketRegime::Volatility { window: 2 }.labels(&returns[..n]);
+            assert_eq!(prefix[..], labels[..n]);
+        }
+        // Calm tail is low relative to the volatile history before it
+        assert_eq!(labels[7].as_deref(), Some("low_vol"));
+    }
+}
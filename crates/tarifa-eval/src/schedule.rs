@@ -0,0 +1,276 @@
+//! Calendar-aware rebalancing schedules.
+//!
+//! A [`RebalanceSchedule`] decides on which of the backtest dates the
+//! portfolio is rebalanced. Calendar rules are resolved against the dates
+//! actually present in the data, so holidays and missing days shift a
+//! rebalance to the nearest available trading date instead of skipping it.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use tarifa_traits::TarifaError;
+
+/// When to rebalance the portfolio.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RebalanceSchedule {
+    /// Every `n` periods, starting with the first date
+    EveryNDays(usize),
+    /// Last available date of each month
+    MonthEnd,
+    /// First available date of each month
+    MonthStart,
+    /// Last available date of each calendar quarter
+    QuarterEnd,
+    /// Every date falling on the given weekday
+    Weekday(Weekday),
+    /// First available date on or after each listed date
+    Dates(Vec<NaiveDate>),
+}
+
+impl Default for RebalanceSchedule {
+    fn default() -> Self {
+        Self::EveryNDays(21)
+    }
+}
+
+impl RebalanceSchedule {
+    /// Flag the rebalance dates among `dates`.
+    ///
+    /// Dates are assumed to be sorted in ascending order. Month and quarter
+    /// boundaries are detected from neighbouring dates, so the first date of
+    /// the slice counts as a month start and the last date as a month and
+    /// quarter end.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::RebalanceSchedule;
+    ///
+    /// let flags = RebalanceSchedule::MonthEnd.resolve(&dates);
+    /// assert_eq!(flags.len(), dates.len());
+    /// ```
+    pub fn resolve(&self, dates: &[NaiveDate]) -> Vec<bool> {
+        let n = dates.len();
+        let month = |d: &NaiveDate| (d.year(), d.month());
+        let quarter = |d: &NaiveDate| (d.year(), d.month0() / 3);
+
+        match self {
+            Self::EveryNDays(every) => (0..n).map(|i| i % (*every).max(1) == 0).collect(),
+            Self::MonthEnd => (0..n)
+                .map(|i| i + 1 == n || month(&dates[i]) != month(&dates[i + 1]))
+                .collect(),
+            Self::MonthStart => (0..n)
+                .map(|i| i == 0 || month(&dates[i]) != month(&dates[i - 1]))
+                .collect(),
+            Self::QuarterEnd => (0..n)
+                .map(|i| i + 1 == n || quarter(&dates[i]) != quarter(&dates[i + 1]))
+                .collect(),
+            Self::Weekday(weekday) => dates.iter().map(|d| d.weekday() == *weekday).collect(),
+            Self::Dates(targets) => {
+                let mut flags = vec![false; n];
+                for target in targets {
+                    let i = dates.partition_point(|d| d < target);
+                    if i < n {
+                        flags[i] = true;
+                    }
+                }
+                flags
+            }
+        }
+    }
+
+    /// Indices of the rebalance dates among `dates`.
+    pub fn rebalance_indices(&self, dates: &[NaiveDate]) -> Vec<usize> {
+        self.resolve(dates)
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &rebalance)| rebalance.then_some(i))
+            .collect()
+    }
+}
+
+impl fmt::Display for RebalanceSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EveryNDays(n) => write!(f, "every:{n}"),
+            Self::MonthEnd => write!(f, "month-end"),
+            Self::MonthStart => write!(f, "month-start"),
+            Self::QuarterEnd => write!(f, "quarter-end"),
+            Self::Weekday(weekday) => write!(f, "{}", weekday.to_string().to_lowercase()),
+            Self::Dates(dates) => {
+                let dates: Vec<String> = dates.iter().map(NaiveDate::to_string).collect();
+                write!(f, "dates:{}", dates.join(","))
+            }
+        }
+    }
+}
+
+impl FromStr for RebalanceSchedule {
+    type Err = TarifaError;
+
+    /// Parse a schedule specification.
+    ///
+    /// Accepts `<n>` or `every:<n>`, `month-end`, `month-start`,
+    /// `quarter-end`, a weekday name (`monday`, `fri`, ...) and
+    /// `dates:<YYYY-MM-DD>,<YYYY-MM-DD>,...`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let spec = s.trim().to_lowercase();
+
+        if let Some(list) = spec.strip_prefix("dates:") {
+            let mut dates = list
+                .split(',')
+                .map(str::trim)
+                .filter(|d| !d.is_empty())
+                .map(|d| {
+                    NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                        .map_err(|_| TarifaError::InvalidDate(d.to_string()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            dates.sort();
+            dates.dedup();
+            return Ok(Self::Dates(dates));
+        }
+
+        let every = spec.strip_prefix("every:").unwrap_or(&spec);
+        if let Ok(n) = every.parse::<usize>() {
+            return if n > 0 {
+                Ok(Self::EveryNDays(n))
+            } else {
+                Err(TarifaError::InvalidData(
+                    "Rebalance interval must be at least 1".to_string(),
+                ))
+            };
+        }
+
+        match spec.as_str() {
+            "month-end" => Ok(Self::MonthEnd),
+            "month-start" => Ok(Self::MonthStart),
+            "quarter-end" => Ok(Self::QuarterEnd),
+            other => other
+                .parse::<Weekday>()
+                .map(Self::Weekday)
+                .map_err(|_| TarifaError::InvalidData(format!("Unknown rebalance schedule '{s}'"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    /// Weekdays from 2024-01-25 through 2024-04-05.
+    fn business_days() -> Vec<NaiveDate> {
+        date(1, 25)
+            .iter_days()
+            .take_while(|d| *d <= date(4, 5))
+            .filter(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+            .collect()
+    }
+
+    fn selected(schedule: &RebalanceSchedule, dates: &[NaiveDate]) -> Vec<NaiveDate> {
+        schedule
+            .rebalance_indices(dates)
+            .into_iter()
+            .map(|i| dates[i])
+            .collect()
+    }
+
+    #[test]
+    fn test_every_n_days() {
+        let dates = business_days();
+        let indices = RebalanceSchedule::EveryNDays(5).rebalance_indices(&dates);
+        assert_eq!(&indices[..3], &[0, 5, 10]);
+
+        // Zero is treated as every period
+        let flags = RebalanceSchedule::EveryNDays(0).resolve(&dates);
+        assert!(flags.iter().all(|&f| f));
+    }
+
+    #[test]
+    fn test_month_boundaries() {
+        let dates = business_days();
+
+        // 2024-03-30/31 fall on a weekend, so March ends on Friday the 29th
+        assert_eq!(
+            selected(&RebalanceSchedule::MonthEnd, &dates),
+            vec![date(1, 31), date(2, 29), date(3, 29), date(4, 5)]
+        );
+        assert_eq!(
+            selected(&RebalanceSchedule::MonthStart, &dates),
+            vec![date(1, 25), date(2, 1), date(3, 1), date(4, 1)]
+        );
+        assert_eq!(
+            selected(&RebalanceSchedule::QuarterEnd, &dates),
+            vec![date(3, 29), date(4, 5)]
+        );
+    }
+
+    #[test]
+    fn test_weekday() {
+        let dates = business_days();
+        let fridays = selected(&RebalanceSchedule::Weekday(Weekday::Fri), &dates);
+
+        assert_eq!(fridays.first(), Some(&date(1, 26)));
+        assert!(fridays.iter().all(|d| d.weekday() == Weekday::Fri));
+    }
+
+    #[test]
+    fn test_explicit_dates() {
+        let dates = business_days();
+        // 2024-02-10 is a Saturday and rolls forward to Monday the 12th
+        let schedule = RebalanceSchedule::Dates(vec![date(2, 10), date(3, 4), date(12, 31)]);
+
+        assert_eq!(selected(&schedule, &dates), vec![date(2, 12), date(3, 4)]);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "21".parse::<RebalanceSchedule>().unwrap(),
+            RebalanceSchedule::EveryNDays(21)
+        );
+        assert_eq!(
+            "every:5".parse::<RebalanceSchedule>().unwrap(),
+            RebalanceSchedule::EveryNDays(5)
+        );
+        assert_eq!(
+            "Month-End".parse::<RebalanceSchedule>().unwrap(),
+            RebalanceSchedule::MonthEnd
+        );
+        assert_eq!(
+            "fri".parse::<RebalanceSchedule>().unwrap(),
+            RebalanceSchedule::Weekday(Weekday::Fri)
+        );
+        assert_eq!(
+            "dates:2024-03-01, 2024-01-31"
+                .parse::<RebalanceSchedule>()
+                .unwrap(),
+            RebalanceSchedule::Dates(vec![date(1, 31), date(3, 1)])
+        );
+
+        assert!("0".parse::<RebalanceSchedule>().is_err());
+        assert!("fortnightly".parse::<RebalanceSchedule>().is_err());
+        assert!(matches!(
+            "dates:2024-13-01".parse::<RebalanceSchedule>(),
+            Err(TarifaError::InvalidDate(_))
+        ));
+
+        // Display output round-trips
+        for schedule in [
+            RebalanceSchedule::QuarterEnd,
+            RebalanceSchedule::Weekday(Weekday::Mon),
+            RebalanceSchedule::Dates(vec![date(1, 31)]),
+        ] {
+            assert_eq!(
+                schedule.to_string().parse::<RebalanceSchedule>().unwrap(),
+                schedule
+            );
+        }
+    }
+}
@@ -0,0 +1,333 @@
+//! Statistical significance under multiple testing.
+//!
+//! Researching many signals and parameter sets inflates the best observed
+//! performance. This module provides the Probabilistic and Deflated Sharpe
+//! ratios of Bailey and Lopez de Prado, and Bonferroni, Holm and
+//! Benjamini-Hochberg adjustments for the IC t-statistics of a batch of
+//! signals.
+
+use serde::{Deserialize, Serialize};
+
+use crate::metrics::t_stat;
+use crate::performance::{excess_kurtosis, inverse_normal_cdf, normal_cdf, skewness};
+use crate::stats::finite_mean;
+
+/// Euler-Mascheroni constant.
+const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+
+/// Probabilistic Sharpe Ratio.
+///
+/// Probability that the true (per-period) Sharpe ratio exceeds
+/// `benchmark_sharpe`, accounting for sample length, skewness and kurtosis of
+/// the returns. `benchmark_sharpe` is per period, not annualized.
+pub fn probabilistic_sharpe_ratio(returns: &[f64], benchmark_sharpe: f64) -> f64 {
+    let r: Vec<f64> = returns.iter().copied().filter(|x| x.is_finite()).collect();
+    let sharpe = per_period_sharpe(&r);
+    let se = sharpe_std_error(&r, sharpe);
+    if !sharpe.is_finite() || !se.is_finite() || se <= 0.0 {
+        return f64::NAN;
+    }
+    normal_cdf((sharpe - benchmark_sharpe) / se)
+}
+
+/// Sharpe ratio deflated for the number of strategies tried.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeflatedSharpe {
+    /// Observed Sharpe ratio (per period)
+    pub sharpe_ratio: f64,
+    /// Probability that the true Sharpe ratio exceeds zero
+    pub probabilistic_sharpe: f64,
+    /// Expected maximum Sharpe ratio (per period) among `n_trials` unskilled strategies
+    pub expected_max_sharpe: f64,
+    /// Probability that the true Sharpe ratio exceeds `expected_max_sharpe`
+    pub deflated_sharpe: f64,
+    /// Number of independent trials
+    pub n_trials: usize,
+    /// Number of finite returns
+    pub n_obs: usize,
+}
+
+impl DeflatedSharpe {
+    /// Compute the Deflated Sharpe Ratio.
+    ///
+    /// # Arguments
+    ///
+    /// * `returns` - Returns of the selected strategy
+    /// * `n_trials` - Number of independent strategies or parameter sets tried
+    /// * `trial_sharpe_variance` - Variance of the per-period Sharpe ratios
+    ///   across trials; `None` uses the sampling variance of a zero Sharpe
+    ///   ratio, `1 / (n - 1)`
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::DeflatedSharpe;
+    ///
+    /// let dsr = DeflatedSharpe::compute(&result.returns, 40, None);
+    /// println!("DSR: {:.3}", dsr.deflated_sharpe);
+    /// ```
+    pub fn compute(returns: &[f64], n_trials: usize, trial_sharpe_variance: Option<f64>) -> Self {
+        let r: Vec<f64> = returns.iter().copied().filter(|x| x.is_finite()).collect();
+        let n_obs = r.len();
+        let sharpe_ratio = per_period_sharpe(&r);
+
+        let variance = trial_sharpe_variance.unwrap_or(if n_obs > 1 {
+            1.0 / (n_obs - 1) as f64
+        } else {
+            f64::NAN
+        });
+        let expected_max_sharpe = expected_max_sharpe(n_trials, variance);
+
+        Self {
+            sharpe_ratio,
+            probabilistic_sharpe: probabilistic_sharpe_ratio(&r, 0.0),
+            expected_max_sharpe,
+            deflated_sharpe: probabilistic_sharpe_ratio(&r, expected_max_sharpe),
+            n_trials,
+            n_obs,
+        }
+    }
+}
+
+/// Expected maximum of `n_trials` Sharpe ratios drawn with zero mean and the
+/// given variance (False Strategy Theorem).
+pub fn expected_max_sharpe(n_trials: usize, variance: f64) -> f64 {
+    if n_trials <= 1 {
+        return 0.0;
+    }
+    let n = n_trials as f64;
+    variance.sqrt()
+        * ((1.0 - EULER_GAMMA) * inverse_normal_cdf(1.0 - 1.0 / n)
+            + EULER_GAMMA * inverse_normal_cdf(1.0 - 1.0 / (n * std::f64::consts::E)))
+}
+
+/// Multiple-testing correction applied to a batch of p-values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Correction {
+    /// No adjustment
+    None,
+    /// Bonferroni family-wise error control
+    Bonferroni,
+    /// Holm step-down family-wise error control
+    Holm,
+    /// Benjamini-Hochberg false discovery rate control
+    BenjaminiHochberg,
+}
+
+/// Adjust p-values for multiple testing.
+///
+/// Adjusted values are returned in the input order and capped at one.
+/// Non-finite p-values are passed through and do not count as tests.
+pub fn adjust_p_values(p_values: &[f64], correction: Correction) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..p_values.len())
+        .filter(|&i| p_values[i].is_finite())
+        .collect();
+    order.sort_by(|&a, &b| p_values[a].total_cmp(&p_values[b]));
+    let m = order.len() as f64;
+    let mut adjusted = p_values.to_vec();
+
+    match correction {
+        Correction::None => {}
+        Correction::Bonferroni => {
+            for &i in &order {
+                adjusted[i] = (p_values[i] * m).min(1.0);
+            }
+        }
+        Correction::Holm => {
+            let mut running = 0.0_f64;
+            for (rank, &i) in order.iter().enumerate() {
+                running = running.max((p_values[i] * (m - rank as f64)).min(1.0));
+                adjusted[i] = running;
+            }
+        }
+        Correction::BenjaminiHochberg => {
+            let mut running = 1.0_f64;
+            for (rank, &i) in order.iter().enumerate().rev() {
+                running = running.min(p_values[i] * m / (rank + 1) as f64);
+                adjusted[i] = running;
+            }
+        }
+    }
+
+    adjusted
+}
+
+/// Significance of one signal's mean IC within a batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IcSignificance {
+    /// Mean IC
+    pub mean_ic: f64,
+    /// t-statistic of the mean IC
+    pub t_stat: f64,
+    /// Two-sided p-value of the t-statistic (normal approximation)
+    pub p_value: f64,
+    /// p-value after the multiple-testing correction
+    pub adjusted_p_value: f64,
+    /// Whether the adjusted p-value is below the significance level
+    pub significant: bool,
+    /// Number of finite IC observations
+    pub n_obs: usize,
+}
+
+impl IcSignificance {
+    /// Test the mean IC of each signal in a batch and correct for multiple testing.
+    ///
+    /// # Arguments
+    ///
+    /// * `ic_series` - One IC time series per signal
+    /// * `correction` - Multiple-testing correction
+    /// * `alpha` - Significance level applied to the adjusted p-values
+    ///
+    /// # Returns
+    ///
+    /// One entry per signal, in input order
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_eval::{Correction, IcSignificance};
+    ///
+    /// let tests = IcSignificance::batch(&ic_series, Correction::Holm, 0.05);
+    /// let survivors = tests.iter().filter(|t| t.significant).count();
+    /// ```
+    pub fn batch(ic_series: &[Vec<f64>], correction: Correction, alpha: f64) -> Vec<Self> {
+        let raw: Vec<(f64, f64, usize)> = ic_series.iter().map(|ic| ic_t_stat(ic)).collect();
+        let p_values: Vec<f64> = raw
+            .iter()
+            .map(|&(_, t, _)| 2.0 * (1.0 - normal_cdf(t.abs())))
+            .collect();
+        let adjusted = adjust_p_values(&p_values, correction);
+
+        raw.into_iter()
+            .zip(p_values)
+            .zip(adjusted)
+            .map(
+                |(((mean_ic, t_stat, n_obs), p_value), adjusted_p_value)| Self {
+                    mean_ic,
+                    t_stat,
+                    p_value,
+                    adjusted_p_value,
+                    significant: adjusted_p_value < alpha,
+                    n_obs,
+                },
+            )
+            .collect()
+    }
+}
+
+/// Mean, t-statistic and observation count of an IC series.
+fn ic_t_stat(ic_series: &[f64]) -> (f64, f64, usize) {
+    let ic: Vec<f64> = ic_series
+        .iter()
+        .copied()
+        .filter(|x| x.is_finite())
+        .collect();
+    let n = ic.len();
+    if n < 2 {
+        return (f64::NAN, f64::NAN, n);
+    }
+    (finite_mean(&ic), t_stat(&ic, None), n)
+}
+
+fn per_period_sharpe(returns: &[f64]) -> f64 {
+    t_stat(returns, None) / (returns.len() as f64).sqrt()
+}
+
+/// Standard error of a Sharpe estimate under non-normal returns (Mertens, 2002).
+fn sharpe_std_error(returns: &[f64], sharpe: f64) -> f64 {
+    let n = returns.len();
+    if n < 4 {
+        return f64::NAN;
+    }
+    let skew = skewness(returns);
+    let kurt = excess_kurtosis(returns);
+    let variance = 1.0 - skew * sharpe + (kurt + 2.0) / 4.0 * sharpe.powi(2);
+    (variance / (n - 1) as f64).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::performance::sample_returns;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_probabilistic_sharpe() {
+        let r = sample_returns();
+        let psr = probabilistic_sharpe_ratio(&r, 0.0);
+
+        // Positive observed Sharpe, so the probability is above one half
+        assert!(psr > 0.5 && psr < 1.0);
+        assert!(probabilistic_sharpe_ratio(&r, 1.0) < psr);
+        assert!(probabilistic_sharpe_ratio(&[0.01, 0.02], 0.0).is_nan());
+    }
+
+    #[test]
+    fn test_deflated_sharpe() {
+        let r = sample_returns();
+        let single = DeflatedSharpe::compute(&r, 1, None);
+        let many = DeflatedSharpe::compute(&r, 100, None);
+
+        assert_relative_eq!(single.deflated_sharpe, single.probabilistic_sharpe);
+        assert!(many.expected_max_sharpe > 0.0);
+        assert!(many.deflated_sharpe < single.deflated_sharpe);
+        assert_eq!(many.n_obs, 12);
+    }
+
+    #[test]
+    fn test_expected_max_sharpe_grows_with_trials() {
+        assert_relative_eq!(expected_max_sharpe(1, 1.0), 0.0);
+        let ten = expected_max_sharpe(10, 1.0);
+        let thousand = expected_max_sharpe(1000, 1.0);
+        assert!(ten > 1.0 && thousand > ten);
+        assert_relative_eq!(expected_max_sharpe(10, 4.0), 2.0 * ten, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_adjust_p_values() {
+        let p = vec![0.01, 0.04, 0.03, 0.005];
+
+        let bonferroni = adjust_p_values(&p, Correction::Bonferroni);
+        assert_relative_eq!(bonferroni[0], 0.04);
+        assert_relative_eq!(bonferroni[1], 0.16);
+
+        // Sorted: 0.005*4, 0.01*3, 0.03*2, 0.04*1 with running max
+        let holm = adjust_p_values(&p, Correction::Holm);
+        assert_relative_eq!(holm[3], 0.02);
+        assert_relative_eq!(holm[0], 0.03);
+        assert_relative_eq!(holm[2], 0.06);
+        assert_relative_eq!(holm[1], 0.06);
+
+        // Sorted: 0.005*4/1, 0.01*4/2, 0.03*4/3, 0.04*4/4 with running min from the top
+        let bh = adjust_p_values(&p, Correction::BenjaminiHochberg);
+        assert_relative_eq!(bh[3], 0.02);
+        assert_relative_eq!(bh[0], 0.02);
+        assert_relative_eq!(bh[2], 0.04);
+        assert_relative_eq!(bh[1], 0.04);
+
+        assert_eq!(adjust_p_values(&p, Correction::None), p);
+    }
+
+    #[test]
+    fn test_adjust_p_values_skips_nan() {
+        let adjusted = adjust_p_values(&[0.02, f64::NAN], Correction::Bonferroni);
+        assert_relative_eq!(adjusted[0], 0.02);
+        assert!(adjusted[1].is_nan());
+    }
+
+    #[test]
+    fn test_ic_significance_batch() {
+        let strong = vec![0.05, 0.04, 0.06, 0.05, 0.03, 0.05, 0.04, 0.06];
+        let noise = vec![0.02, -0.03, 0.01, -0.02, 0.03, -0.01, 0.0, -0.01];
+        let tests =
+            IcSignificance::batch(&[strong, noise, vec![0.1]], Correction::Bonferroni, 0.05);
+
+        assert_eq!(tests.len(), 3);
+        assert!(tests[0].significant);
+        assert!(tests[0].t_stat > 0.0);
+        assert!(!tests[1].significant);
+        assert!(tests[1].adjusted_p_value >= tests[1].p_value);
+        assert!(tests[2].t_stat.is_nan());
+        assert!(!tests[2].significant);
+    }
+}
@@ -0,0 +1,53 @@
+//! Summary statistics shared across the evaluation modules.
+//!
+//! Signal and return series routinely contain NaN for periods without enough
+//! data; [`finite_mean`] skips non-finite values, while [`mean`] and
+//! [`std_dev`] expect callers to have filtered them already.
+
+/// Mean of the finite values, NaN if there are none.
+pub(crate) fn finite_mean(values: &[f64]) -> f64 {
+    let (sum, n) = values
+        .iter()
+        .filter(|x| x.is_finite())
+        .fold((0.0, 0usize), |(s, n), x| (s + x, n + 1));
+    if n == 0 { f64::NAN } else { sum / n as f64 }
+}
+
+/// Arithmetic mean, NaN if empty.
+pub(crate) fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        f64::NAN
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// Sample standard deviation (`n - 1` denominator), NaN with fewer than two values.
+pub(crate) fn std_dev(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return f64::NAN;
+    }
+    let m = mean(values);
+    (values.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (values.len() - 1) as f64).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finite_mean() {
+        assert_eq!(finite_mean(&[1.0, f64::NAN, 3.0, f64::INFINITY]), 2.0);
+        assert!(finite_mean(&[f64::NAN]).is_nan());
+        assert!(finite_mean(&[]).is_nan());
+    }
+
+    #[test]
+    fn test_mean_std_dev() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_eq!(mean(&values), 5.0);
+        assert!((std_dev(&values) - (32.0_f64 / 7.0).sqrt()).abs() < 1e-12);
+        assert!(mean(&[]).is_nan());
+        assert!(std_dev(&[1.0]).is_nan());
+    }
+}
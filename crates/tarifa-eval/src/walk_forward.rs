@@ -0,0 +1,609 @@
+//! Walk-forward validation.
+//!
+//! Splits a date range into consecutive train/test windows, fits a stateful
+//! model on each train window and evaluates it on the test window that
+//! follows. Test-window returns are stitched into one out-of-sample series and
+//! compared with in-sample performance to measure degradation.
+//!
+//! Any model implementing [`WalkForwardModel`] can be validated;
+//! [`ICWeightedModel`] re-estimates the IC history of an
+//! [`ICWeightedCombiner`] on every train window.
+
+use std::ops::Range;
+
+use chrono::NaiveDate;
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+use tarifa_combine::{Combiner, ICWeightedCombiner, ICWeightedConfig, SignalScore};
+use tarifa_traits::{Result, TarifaError};
+
+use crate::backtest::{Backtest, BacktestConfig};
+use crate::ic::calculate_ic;
+use crate::performance::{self, PerformanceStats};
+use crate::stats::finite_mean;
+
+/// How train windows move through time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WindowScheme {
+    /// Fixed-length train window that rolls forward with the test window
+    #[default]
+    Rolling,
+    /// Train window anchored at the first period and growing over time
+    Expanding,
+}
+
+/// Walk-forward configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardConfig {
+    /// Periods in each train window (the initial window when expanding)
+    pub train_periods: usize,
+    /// Periods in each test window; windows advance by this amount
+    pub test_periods: usize,
+    /// Rolling or expanding train windows
+    pub scheme: WindowScheme,
+    /// Periods per year used for annualization
+    pub periods_per_year: usize,
+}
+
+impl Default for WalkForwardConfig {
+    fn default() -> Self {
+        Self {
+            train_periods: 252,
+            test_periods: 63,
+            scheme: WindowScheme::Rolling,
+            periods_per_year: 252,
+        }
+    }
+}
+
+/// One train/test split, as period indices and the dates they span.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalkForwardWindow {
+    /// Train period indices
+    pub train: Range<usize>,
+    /// Test period indices (immediately after the train window)
+    pub test: Range<usize>,
+    /// First train date
+    pub train_start: NaiveDate,
+    /// Last train date
+    pub train_end: NaiveDate,
+    /// First test date
+    pub test_start: NaiveDate,
+    /// Last test date
+    pub test_end: NaiveDate,
+}
+
+/// A model that can be re-fitted on each train window.
+///
+/// The model owns its data; period indices passed to it line up with the
+/// dates given to [`WalkForward::run`].
+pub trait WalkForwardModel {
+    /// Fit the model on the given train periods, replacing any previous fit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model cannot be fitted on these periods.
+    fn fit(&mut self, train: Range<usize>) -> Result<()>;
+
+    /// Per-period returns of the fitted model over the given periods.
+    ///
+    /// Must return one value per period.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model cannot be evaluated on these periods.
+    fn evaluate(&self, periods: Range<usize>) -> Result<Vec<f64>>;
+
+    /// Per-period returns of the fitted model over a test window.
+    ///
+    /// Test windows are evaluated in order, each starting where the previous
+    /// one ended, so implementations can carry state such as held positions
+    /// from one window into the next. Defaults to [`WalkForwardModel::evaluate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model cannot be evaluated on these periods.
+    fn evaluate_out_of_sample(&mut self, test: Range<usize>) -> Result<Vec<f64>> {
+        self.evaluate(test)
+    }
+}
+
+/// In-sample and out-of-sample performance of one window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowResult {
+    /// The train/test split
+    pub window: WalkForwardWindow,
+    /// Annualized Sharpe ratio over the train window
+    pub in_sample_sharpe: f64,
+    /// Annualized Sharpe ratio over the test window
+    pub out_of_sample_sharpe: f64,
+    /// Annualized mean return over the train window
+    pub in_sample_return: f64,
+    /// Annualized mean return over the test window
+    pub out_of_sample_return: f64,
+}
+
+/// Walk-forward validation results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardResult {
+    /// Per-window results, in time order
+    pub windows: Vec<WindowResult>,
+    /// Stitched out-of-sample returns
+    pub oos_returns: Vec<f64>,
+    /// Date of each out-of-sample return
+    pub oos_dates: Vec<NaiveDate>,
+    /// Mean of the per-window in-sample Sharpe ratios
+    pub in_sample_sharpe: f64,
+    /// Sharpe ratio of the stitched out-of-sample returns
+    pub out_of_sample_sharpe: f64,
+    /// Share of in-sample Sharpe lost out of sample (`1 - oos / is`)
+    pub sharpe_degradation: f64,
+    /// Performance statistics of the stitched out-of-sample returns
+    pub out_of_sample: PerformanceStats,
+}
+
+/// Walk-forward validation driver.
+#[derive(Debug, Clone, Default)]
+pub struct WalkForward {
+    config: WalkForwardConfig,
+}
+
+impl WalkForward {
+    /// Create a walk-forward driver.
+    pub const fn new(config: WalkForwardConfig) -> Self {
+        Self { config }
+    }
+
+    /// Split `dates` into train/test windows.
+    ///
+    /// The last test window is shortened to the end of the data; no window is
+    /// produced if the data does not extend past the first train window.
+    pub fn windows(&self, dates: &[NaiveDate]) -> Vec<WalkForwardWindow> {
+        let n = dates.len();
+        let (train_len, test_len) = (self.config.train_periods, self.config.test_periods);
+        if train_len == 0 || test_len == 0 {
+            return Vec::new();
+        }
+
+        let mut windows = Vec::new();
+        let mut test_start = train_len;
+        while test_start < n {
+            let train_start = match self.config.scheme {
+                WindowScheme::Rolling => test_start - train_len,
+                WindowScheme::Expanding => 0,
+            };
+            let test_end = (test_start + test_len).min(n);
+            windows.push(WalkForwardWindow {
+                train: train_start..test_start,
+                test: test_start..test_end,
+                train_start: dates[train_start],
+                train_end: dates[test_start - 1],
+                test_start: dates[test_start],
+                test_end: dates[test_end - 1],
+            });
+            test_start = test_end;
+        }
+
+        windows
+    }
+
+    /// Run walk-forward validation.
+    ///
+    /// # Arguments
+    ///
+    /// * `dates` - Period dates, indexed like the model's data
+    /// * `model` - Model to fit and evaluate on each window
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is not enough data for a single window, if
+    /// the model fails to fit or evaluate, or if it returns the wrong number
+    /// of periods.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tarifa_combine::ICWeightedConfig;
+    /// use tarifa_eval::{ICWeightedModel, WalkForward, WalkForwardConfig};
+    ///
+    /// let mut model = ICWeightedModel::new(returns, dates.clone(), ICWeightedConfig::default())
+    ///     .with_signal("momentum", momentum_scores)
+    ///     .with_signal("value", value_scores);
+    /// let result = WalkForward::new(WalkForwardConfig::default()).run(&dates, &mut model)?;
+    /// println!("OOS Sharpe: {:.2}", result.out_of_sample_sharpe);
+    /// ```
+    pub fn run(
+        &self,
+        dates: &[NaiveDate],
+        model: &mut impl WalkForwardModel,
+    ) -> Result<WalkForwardResult> {
+        let windows = self.windows(dates);
+        if windows.is_empty() {
+            return Err(TarifaError::InsufficientData(format!(
+                "{} periods cannot fill a {}-period train window and a test window",
+                dates.len(),
+                self.config.train_periods
+            )));
+        }
+
+        let annual = self.config.periods_per_year;
+        let mut results = Vec::with_capacity(windows.len());
+        let mut oos_returns = Vec::new();
+        let mut oos_dates = Vec::new();
+
+        for window in windows {
+            model.fit(window.train.clone())?;
+            let in_sample = checked(model.evaluate(window.train.clone())?, &window.train)?;
+            let out_of_sample = checked(
+                model.evaluate_out_of_sample(window.test.clone())?,
+                &window.test,
+            )?;
+
+            oos_dates.extend_from_slice(&dates[window.test.clone()]);
+            oos_returns.extend_from_slice(&out_of_sample);
+            results.push(WindowResult {
+                in_sample_sharpe: performance::sharpe_ratio(&in_sample, annual),
+                out_of_sample_sharpe: performance::sharpe_ratio(&out_of_sample, annual),
+                in_sample_return: finite_mean(&in_sample) * annual as f64,
+                out_of_sample_return: finite_mean(&out_of_sample) * annual as f64,
+                window,
+            });
+        }
+
+        let in_sample_sharpe = finite_mean(
+            &results
+                .iter()
+                .map(|w| w.in_sample_sharpe)
+                .collect::<Vec<_>>(),
+        );
+        let out_of_sample_sharpe = performance::sharpe_ratio(&oos_returns, annual);
+        let sharpe_degradation = if in_sample_sharpe.is_finite() && in_sample_sharpe != 0.0 {
+            1.0 - out_of_sample_sharpe / in_sample_sharpe
+        } else {
+            f64::NAN
+        };
+
+        Ok(WalkForwardResult {
+            out_of_sample: PerformanceStats::compute(&oos_returns, &oos_dates, annual),
+            windows: results,
+            oos_returns,
+            oos_dates,
+            in_sample_sharpe,
+            out_of_sample_sharpe,
+            sharpe_degradation,
+        })
+    }
+}
+
+/// Walk-forward model that combines signals with an [`ICWeightedCombiner`].
+///
+/// Fitting rebuilds the combiner's IC history from the cross-sectional IC of
+/// each signal over the train window. Evaluation combines the signals with the
+/// fitted weights and backtests the composite. Non-finite scores are treated
+/// as neutral (zero) before combining.
+///
+/// In-sample evaluations backtest the train window on its own. Consecutive
+/// test windows are backtested as one run over the stitched composite, so the
+/// book and the rebalance schedule carry over from one window to the next and
+/// trades into each new window's target are costed like any other rebalance.
+#[derive(Debug)]
+pub struct ICWeightedModel {
+    signals: Vec<(String, Vec<Vec<f64>>)>,
+    returns: Vec<Vec<f64>>,
+    dates: Vec<NaiveDate>,
+    config: ICWeightedConfig,
+    combiner: ICWeightedCombiner,
+    backtest: Backtest,
+    /// First period of the stitched out-of-sample run
+    oos_start: usize,
+    /// Composite scores of the out-of-sample periods evaluated so far
+    oos_composite: Vec<Vec<f64>>,
+}
+
+impl ICWeightedModel {
+    /// Create a model without signals.
+    ///
+    /// # Arguments
+    ///
+    /// * `returns` - Forward returns (periods x assets): `returns[t]` is
+    ///   earned by positions taken on `dates[t]`
+    /// * `dates` - Period dates
+    /// * `config` - Configuration of the IC-weighted combiner
+    pub fn new(returns: Vec<Vec<f64>>, dates: Vec<NaiveDate>, config: ICWeightedConfig) -> Self {
+        let backtest = Backtest::new(BacktestConfig::default());
+        Self {
+            signals: Vec::new(),
+            returns,
+            dates,
+            combiner: ICWeightedCombiner::new(config.clone()),
+            config,
+            backtest,
+            oos_start: 0,
+            oos_composite: Vec::new(),
+        }
+    }
+
+    /// Add a signal's scores (periods x assets, aligned with the returns).
+    pub fn with_signal(mut self, name: impl Into<String>, scores: Vec<Vec<f64>>) -> Self {
+        self.signals.push((name.into(), scores));
+        self
+    }
+
+    /// Backtest composites with this engine instead of the default.
+    ///
+    /// The default backtest uses [`BacktestConfig::default`], which has no
+    /// date window. A custom engine's date window still applies, so evaluated
+    /// periods outside it are dropped.
+    pub fn with_backtest(mut self, backtest: Backtest) -> Self {
+        self.backtest = backtest;
+        self
+    }
+
+    /// The fitted combiner.
+    pub const fn combiner(&self) -> &ICWeightedCombiner {
+        &self.combiner
+    }
+
+    fn composite(&self, t: usize) -> Result<Vec<f64>> {
+        let signals: Vec<SignalScore> = self
+            .signals
+            .iter()
+            .map(|(name, scores)| SignalScore {
+                name: name.clone(),
+                scores: scores[t]
+                    .iter()
+                    .map(|&s| if s.is_finite() { s } else { 0.0 })
+                    .collect(),
+            })
+            .collect();
+        Ok(self.combiner.combine(&signals)?.to_vec())
+    }
+}
+
+impl WalkForwardModel for ICWeightedModel {
+    fn fit(&mut self, train: Range<usize>) -> Result<()> {
+        self.combiner = ICWeightedCombiner::new(self.config.clone());
+        for t in train {
+            let returns = Array1::from_vec(self.returns[t].clone());
+            for (name, scores) in &self.signals {
+                let ic = calculate_ic(&Array1::from_vec(scores[t].clone()), &returns);
+                if ic.is_finite() {
+                    self.combiner.update_ic(name, ic);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self, periods: Range<usize>) -> Result<Vec<f64>> {
+        let composite = periods
+            .clone()
+            .map(|t| self.composite(t))
+            .collect::<Result<Vec<_>>>()?;
+        let result = self.backtest.run(
+            &composite,
+            &self.returns[periods.clone()],
+            &self.dates[periods],
+        );
+        Ok(result.returns)
+    }
+
+    fn evaluate_out_of_sample(&mut self, test: Range<usize>) -> Result<Vec<f64>> {
+        // A window that does not continue the previous one starts a new book
+        if test.start != self.oos_start + self.oos_composite.len() {
+            self.oos_start = test.start;
+            self.oos_composite.clear();
+        }
+        for t in test.clone() {
+            let composite = self.composite(t)?;
+            self.oos_composite.push(composite);
+        }
+
+        let stitched = self.oos_start..test.end;
+        let result = self.backtest.run(
+            &self.oos_composite,
+            &self.returns[stitched.clone()],
+            &self.dates[stitched],
+        );
+        let skip = result.returns.len().saturating_sub(test.len());
+        Ok(result.returns[skip..].to_vec())
+    }
+}
+
+fn checked(returns: Vec<f64>, periods: &Range<usize>) -> Result<Vec<f64>> {
+    if returns.len() != periods.len() {
+        return Err(TarifaError::InvalidData(format!(
+            "Model returned {} returns for {} periods",
+            returns.len(),
+            periods.len()
+        )));
+    }
+    Ok(returns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::performance::daily_dates;
+    use approx::assert_relative_eq;
+
+    /// Returns the mean of its train window on every evaluated period.
+    struct MeanModel {
+        data: Vec<f64>,
+        fitted: f64,
+    }
+
+    impl WalkForwardModel for MeanModel {
+        fn fit(&mut self, train: Range<usize>) -> Result<()> {
+            self.fitted = finite_mean(&self.data[train]);
+            Ok(())
+        }
+
+        fn evaluate(&self, periods: Range<usize>) -> Result<Vec<f64>> {
+            Ok(periods
+                .map(|t| self.data[t] * self.fitted.signum())
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_rolling_windows() {
+        let dates = daily_dates(10);
+        let wf = WalkForward::new(WalkForwardConfig {
+            train_periods: 4,
+            test_periods: 3,
+            ..Default::default()
+        });
+        let windows = wf.windows(&dates);
+
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].train, 0..4);
+        assert_eq!(windows[0].test, 4..7);
+        assert_eq!(windows[1].train, 3..7);
+        assert_eq!(windows[1].test, 7..10);
+        assert_eq!(windows[1].test_end, dates[9]);
+    }
+
+    #[test]
+    fn test_expanding_windows() {
+        let dates = daily_dates(9);
+        let wf = WalkForward::new(WalkForwardConfig {
+            train_periods: 4,
+            test_periods: 3,
+            scheme: WindowScheme::Expanding,
+            ..Default::default()
+        });
+        let windows = wf.windows(&dates);
+
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[1].train, 0..7);
+        // Final test window is cut at the end of the data
+        assert_eq!(windows[1].test, 7..9);
+    }
+
+    #[test]
+    fn test_run_stitches_out_of_sample() {
+        let dates = daily_dates(12);
+        let data = vec![
+            0.01, 0.02, 0.01, 0.03, -0.01, 0.02, 0.01, -0.02, 0.01, 0.02, 0.00, 0.01,
+        ];
+        let mut model = MeanModel {
+            data: data.clone(),
+            fitted: 0.0,
+        };
+        let wf = WalkForward::new(WalkForwardConfig {
+            train_periods: 4,
+            test_periods: 4,
+            ..Default::default()
+        });
+        let result = wf.run(&dates, &mut model).unwrap();
+
+        assert_eq!(result.windows.len(), 2);
+        assert_eq!(result.oos_returns, data[4..].to_vec());
+        assert_eq!(result.oos_dates, dates[4..].to_vec());
+        assert!(result.in_sample_sharpe > result.out_of_sample_sharpe);
+        assert_relative_eq!(
+            result.sharpe_degradation,
+            1.0 - result.out_of_sample_sharpe / result.in_sample_sharpe
+        );
+    }
+
+    #[test]
+    fn test_run_insufficient_data() {
+        let dates = daily_dates(3);
+        let mut model = MeanModel {
+            data: vec![0.0; 3],
+            fitted: 0.0,
+        };
+        let result = WalkForward::new(WalkForwardConfig::default()).run(&dates, &mut model);
+        assert!(matches!(result, Err(TarifaError::InsufficientData(_))));
+    }
+
+    #[test]
+    fn test_ic_weighted_model() {
+        let n_periods = 40;
+        let dates = daily_dates(n_periods);
+        // Asset j earns 0.001 * (j - 2) each period; "good" ranks assets correctly
+        let returns: Vec<Vec<f64>> = (0..n_periods)
+            .map(|t| {
+                (0..5)
+                    .map(|j| 0.001 * (j as f64 - 2.0) + 0.0005 * ((t + j) % 3) as f64)
+                    .collect()
+            })
+            .collect();
+        let good: Vec<Vec<f64>> = (0..n_periods)
+            .map(|_| (0..5).map(|j| j as f64).collect())
+            .collect();
+        let noise: Vec<Vec<f64>> = (0..n_periods)
+            .map(|t| (0..5).map(|j| ((t * 7 + j * 3) % 5) as f64).collect())
+            .collect();
+
+        let mut model = ICWeightedModel::new(returns, dates.clone(), ICWeightedConfig::default())
+            .with_signal("good", good)
+            .with_signal("noise", noise)
+            .with_backtest(Backtest::new(BacktestConfig {
+                n_long: Some(2),
+                n_short: Some(2),
+                transaction_cost_bps: 0.0,
+                max_position_size: 1.0,
+                ..Default::default()
+            }));
+        let wf = WalkForward::new(WalkForwardConfig {
+            train_periods: 20,
+            test_periods: 10,
+            ..Default::default()
+        });
+        let result = wf.run(&dates, &mut model).unwrap();
+
+        assert_eq!(result.windows.len(), 2);
+        assert_eq!(result.oos_returns.len(), 20);
+        assert!(result.oos_returns.iter().sum::<f64>() > 0.0);
+    }
+
+    #[test]
+    fn test_ic_weighted_model_carries_book() {
+        let n_periods = 40;
+        let dates = daily_dates(n_periods);
+        let returns: Vec<Vec<f64>> = (0..n_periods)
+            .map(|t| {
+                (0..4)
+                    .map(|j| 0.001 * (j as f64 - 1.5) + 0.0005 * ((t + j) % 3) as f64)
+                    .collect()
+            })
+            .collect();
+        let scores: Vec<Vec<f64>> = (0..n_periods)
+            .map(|_| (0..4).map(|j| j as f64).collect())
+            .collect();
+
+        let config = BacktestConfig {
+            rebalance_frequency: 4,
+            transaction_cost_bps: 10.0,
+            long_short: false,
+            n_long: Some(2),
+            max_position_size: 1.0,
+            ..Default::default()
+        };
+        let mut model =
+            ICWeightedModel::new(returns.clone(), dates.clone(), ICWeightedConfig::default())
+                .with_signal("signal", scores.clone())
+                .with_backtest(Backtest::new(config.clone()));
+        let wf = WalkForward::new(WalkForwardConfig {
+            train_periods: 20,
+            test_periods: 10,
+            ..Default::default()
+        });
+        let result = wf.run(&dates, &mut model).unwrap();
+
+        // Test windows behave like one continuous backtest of the same book:
+        // one build, and a schedule that does not restart at period 30
+        let continuous = Backtest::new(config).run(&scores[20..], &returns[20..], &dates[20..]);
+        assert_eq!(result.oos_returns.len(), continuous.returns.len());
+        for (oos, expected) in result.oos_returns.iter().zip(&continuous.returns) {
+            assert_relative_eq!(*oos, *expected, epsilon = 1e-15);
+        }
+
+        // Running again starts a new book rather than extending the old one
+        let again = wf.run(&dates, &mut model).unwrap();
+        assert_eq!(again.oos_returns, result.oos_returns);
+    }
+}
@@ -17,6 +17,7 @@ pub mod alpha;
 pub mod error;
 pub mod evaluator;
 pub mod types;
+pub mod validation;
 
 // Re-exports
 pub use alpha::AlphaModel;
@@ -24,6 +25,7 @@ pub use error::{Result, TarifaError};
 pub use evaluator::FactorEvaluator;
 pub use factors::Factor;
 pub use types::{Date, MarketData, Symbol};
+pub use validation::{CvSplit, PurgedKFold};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,258 @@
+//! Purged and embargoed cross-validation splits.
+//!
+//! Forward returns measured over a horizon overlap across adjacent dates, so a
+//! naive train/test split leaks test-period information into training. This
+//! module implements combinatorial purged K-fold cross-validation (Lopez de
+//! Prado, 2018): periods are cut into contiguous groups, every combination of
+//! test groups forms one split, training periods whose labels overlap a test
+//! period are purged and an embargo is applied after each test block.
+//!
+//! Splits are plain period indices so they can drive signal combiners and
+//! evaluation routines alike.
+
+use serde::{Deserialize, Serialize};
+
+/// One train/test split of period indices.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CvSplit {
+    /// Training period indices, in ascending order, after purging and embargo.
+    pub train: Vec<usize>,
+    /// Test period indices, in ascending order.
+    pub test: Vec<usize>,
+    /// Indices of the groups that form the test set.
+    pub test_groups: Vec<usize>,
+}
+
+impl CvSplit {
+    /// Select the training values of a period-indexed series.
+    ///
+    /// Indices past the end of `series` are skipped.
+    pub fn train_values<T: Copy>(&self, series: &[T]) -> Vec<T> {
+        self.train
+            .iter()
+            .filter_map(|&i| series.get(i).copied())
+            .collect()
+    }
+
+    /// Select the test values of a period-indexed series.
+    ///
+    /// Indices past the end of `series` are skipped.
+    pub fn test_values<T: Copy>(&self, series: &[T]) -> Vec<T> {
+        self.test
+            .iter()
+            .filter_map(|&i| series.get(i).copied())
+            .collect()
+    }
+}
+
+/// Combinatorial purged K-fold cross-validation.
+///
+/// With `n_test_groups = 1` this is ordinary purged K-fold. The label of
+/// period `i` is assumed to span periods `i..=i + horizon`; any training
+/// period whose label overlaps a test label is purged, and the `embargo`
+/// periods following each test block are dropped from training as well.
+///
+/// # Example
+///
+/// ```
+/// use tarifa_traits::PurgedKFold;
+///
+/// let cv = PurgedKFold::new(5, 1).with_horizon(2).with_embargo(1);
+/// let splits = cv.splits(100);
+///
+/// assert_eq!(splits.len(), 5);
+/// // Test block 20..40 purges 18..20 before it and 40..43 after it
+/// assert!(!splits[1].train.contains(&19));
+/// assert!(!splits[1].train.contains(&42));
+/// assert!(splits[1].train.contains(&43));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PurgedKFold {
+    /// Number of contiguous groups the periods are divided into.
+    pub n_groups: usize,
+    /// Number of groups held out for testing in each split.
+    pub n_test_groups: usize,
+    /// Label horizon in periods used for purging.
+    pub horizon: usize,
+    /// Number of periods embargoed after each test block.
+    pub embargo: usize,
+}
+
+impl Default for PurgedKFold {
+    fn default() -> Self {
+        Self::new(5, 1)
+    }
+}
+
+impl PurgedKFold {
+    /// Creates a splitter with no purging horizon or embargo.
+    ///
+    /// # Arguments
+    ///
+    /// * `n_groups` - Number of contiguous groups
+    /// * `n_test_groups` - Number of groups in each test set
+    pub const fn new(n_groups: usize, n_test_groups: usize) -> Self {
+        Self {
+            n_groups,
+            n_test_groups,
+            horizon: 0,
+            embargo: 0,
+        }
+    }
+
+    /// Sets the label horizon used for purging.
+    pub const fn with_horizon(mut self, horizon: usize) -> Self {
+        self.horizon = horizon;
+        self
+    }
+
+    /// Sets the number of periods embargoed after each test block.
+    pub const fn with_embargo(mut self, embargo: usize) -> Self {
+        self.embargo = embargo;
+        self
+    }
+
+    /// Number of splits, `C(n_groups, n_test_groups)`.
+    pub fn n_splits(&self) -> usize {
+        binomial(self.n_groups, self.n_test_groups)
+    }
+
+    /// Number of complete backtest paths the splits can be assembled into,
+    /// `C(n_groups - 1, n_test_groups - 1)`.
+    pub fn n_paths(&self) -> usize {
+        if self.n_groups == 0 || self.n_test_groups == 0 {
+            return 0;
+        }
+        binomial(self.n_groups - 1, self.n_test_groups - 1)
+    }
+
+    /// Generates every train/test split of `n_periods` periods.
+    ///
+    /// Returns no splits if there are fewer periods than groups or the test
+    /// group count is not between 1 and `n_groups - 1`.
+    pub fn splits(&self, n_periods: usize) -> Vec<CvSplit> {
+        let (n_groups, k) = (self.n_groups, self.n_test_groups);
+        if n_groups < 2 || k == 0 || k >= n_groups || n_periods < n_groups {
+            return Vec::new();
+        }
+
+        let bounds: Vec<usize> = (0..=n_groups).map(|g| g * n_periods / n_groups).collect();
+
+        combinations(n_groups, k)
+            .into_iter()
+            .map(|test_groups| {
+                let mut excluded = vec![false; n_periods];
+                let mut test = Vec::new();
+                for &g in &test_groups {
+                    let (start, end) = (bounds[g], bounds[g + 1]);
+                    test.extend(start..end);
+
+                    let purge_start = start.saturating_sub(self.horizon);
+                    let purge_end = (end + self.horizon + self.embargo).min(n_periods);
+                    excluded[purge_start..purge_end].fill(true);
+                }
+                let train = (0..n_periods).filter(|&i| !excluded[i]).collect();
+
+                CvSplit {
+                    train,
+                    test,
+                    test_groups,
+                }
+            })
+            .collect()
+    }
+}
+
+/// All `k`-element subsets of `0..n` in lexicographic order.
+fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    let mut result = Vec::new();
+    let mut current: Vec<usize> = (0..k).collect();
+
+    loop {
+        result.push(current.clone());
+
+        // Advance the rightmost index that still has room
+        let Some(i) = (0..k).rev().find(|&i| current[i] < n - k + i) else {
+            return result;
+        };
+        current[i] += 1;
+        for j in i + 1..k {
+            current[j] = current[j - 1] + 1;
+        }
+    }
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_k_fold() {
+        let splits = PurgedKFold::new(4, 1).splits(20);
+
+        assert_eq!(splits.len(), 4);
+        assert_eq!(splits[0].test, (0..5).collect::<Vec<_>>());
+        assert_eq!(splits[0].train, (5..20).collect::<Vec<_>>());
+        assert_eq!(splits[2].train.len(), 15);
+    }
+
+    #[test]
+    fn test_purge_and_embargo() {
+        let cv = PurgedKFold::new(4, 1).with_horizon(2).with_embargo(1);
+        let splits = cv.splits(20);
+
+        // Test block 5..10 purges 3..5 and 10..12, embargoes 12
+        let split = &splits[1];
+        assert_eq!(split.test, (5..10).collect::<Vec<_>>());
+        assert_eq!(split.train, [0, 1, 2, 13, 14, 15, 16, 17, 18, 19]);
+
+        // Train and test labels never overlap
+        for s in &splits {
+            for &i in &s.train {
+                assert!(s.test.iter().all(|&j| i.abs_diff(j) > cv.horizon));
+            }
+        }
+    }
+
+    #[test]
+    fn test_combinatorial_splits() {
+        let cv = PurgedKFold::new(6, 2);
+        let splits = cv.splits(60);
+
+        assert_eq!(splits.len(), 15);
+        assert_eq!(cv.n_splits(), 15);
+        assert_eq!(cv.n_paths(), 5);
+        assert_eq!(splits[0].test_groups, [0, 1]);
+        assert_eq!(splits[14].test_groups, [4, 5]);
+
+        // Every group is tested in n_paths splits
+        for g in 0..6 {
+            let count = splits.iter().filter(|s| s.test_groups.contains(&g)).count();
+            assert_eq!(count, cv.n_paths());
+        }
+    }
+
+    #[test]
+    fn test_invalid_configuration() {
+        assert!(PurgedKFold::new(1, 1).splits(10).is_empty());
+        assert!(PurgedKFold::new(4, 4).splits(10).is_empty());
+        assert!(PurgedKFold::new(5, 1).splits(3).is_empty());
+    }
+
+    #[test]
+    fn test_split_values() {
+        let splits = PurgedKFold::new(2, 1).splits(4);
+        let series = [0.1, 0.2, 0.3, 0.4];
+
+        assert_eq!(splits[0].test_values(&series), [0.1, 0.2]);
+        assert_eq!(splits[0].train_values(&series), [0.3, 0.4]);
+    }
+}